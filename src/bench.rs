@@ -0,0 +1,179 @@
+//! Benchmarks of the timed stages against a stored baseline
+//!
+//! `aoc bench` times every stage with [`timing::time_day`] and compares the medians with those
+//! in the baseline file. A stage regressed if its median grew by more than the threshold.
+//! Medians of different machines are not comparable, so the baseline is re-recorded with
+//! `--record` where the benchmarks run.
+//!
+//! [`timing::time_day`]: crate::timing::time_day
+
+use std::{
+	collections::BTreeMap,
+	fs, io,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use crate::{
+	days::Part,
+	error::{Error, ParseError},
+	scan,
+	timing::{DayTimings, Stage},
+};
+
+/// The default baseline file, checked into this repository
+pub fn default_path() -> PathBuf {
+	[env!("CARGO_MANIFEST_DIR"), "benchmarks.txt"]
+		.iter()
+		.collect()
+}
+
+/// Key of a stored median: day, part (`None` for the parse stage) and input fingerprint
+type BaselineKey = (u8, Option<Part>, String);
+
+/// Median timings per day, stage and input
+///
+/// The file has one median per line, as `<day> <stage> <input fingerprint> <median ns>`, where
+/// the stage is `parse`, `1` or `2`. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Baseline {
+	medians: BTreeMap<BaselineKey, Duration>,
+}
+
+impl Baseline {
+	/// Load the baseline file, or start empty if it does not exist yet
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let content = match fs::read_to_string(path) {
+			Ok(content) => content,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+			Err(error) => return Err(error.into()),
+		};
+
+		Baseline::parse(&content).map_err(|error| Error::Baseline {
+			path: path.to_path_buf(),
+			error,
+		})
+	}
+
+	/// Parse the content of a baseline file
+	fn parse(content: &str) -> Result<Self, ParseError> {
+		let mut medians = BTreeMap::new();
+
+		for line in content.lines() {
+			if line.trim().is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let mut fields = line.split(' ');
+			let mut next = |expected| {
+				fields
+					.next()
+					.filter(|field| !field.is_empty())
+					.ok_or_else(|| ParseError::at_token(content, &line[line.len()..], expected))
+			};
+			let (day, stage, fingerprint, median) = (
+				next("a day")?,
+				next("a stage")?,
+				next("an input")?,
+				next("a median")?,
+			);
+			if let Some(extra) = fields.next() {
+				return Err(ParseError::at_token(content, extra, "the end of the line"));
+			}
+
+			let day = scan::integer_token(content, day)?;
+			let part = match stage {
+				"parse" => None,
+				"1" => Some(Part::One),
+				"2" => Some(Part::Two),
+				_ => return Err(ParseError::at_token(content, stage, "`parse`, `1` or `2`")),
+			};
+			let median = Duration::from_nanos(scan::integer_token(content, median)?);
+
+			medians.insert((day, part, fingerprint.to_string()), median);
+		}
+
+		Ok(Baseline { medians })
+	}
+
+	/// Write the baseline file
+	pub fn save(&self, path: &Path) -> io::Result<()> {
+		let mut content = String::from("# <day> <stage> <input fingerprint> <median ns>\n");
+		for ((day, part, fingerprint), median) in &self.medians {
+			let stage = part.map_or("parse".to_string(), |part| part.to_string());
+			content.push_str(&format!(
+				"{} {} {} {}\n",
+				day,
+				stage,
+				fingerprint,
+				median.as_nanos()
+			));
+		}
+		fs::write(path, content)
+	}
+
+	/// The stored median of a stage on an input
+	pub fn get(&self, day: u8, stage: Stage, fingerprint: &str) -> Option<Duration> {
+		self.medians
+			.get(&(day, part(stage), fingerprint.to_string()))
+			.copied()
+	}
+
+	/// Store the medians of all stages of a day, replacing those of earlier runs on the input
+	pub fn record(&mut self, timings: &DayTimings) {
+		for timing in &timings.stages {
+			self.medians.insert(
+				(timings.day, part(timing.stage), timings.fingerprint.clone()),
+				timing.stats.median,
+			);
+		}
+	}
+}
+
+/// The part of a stage, `None` for the parse stage
+fn part(stage: Stage) -> Option<Part> {
+	match stage {
+		Stage::Parse => None,
+		Stage::Part(part) => Some(part),
+	}
+}
+
+/// The median of a stage compared with its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+	pub day: u8,
+	pub stage: Stage,
+	/// The stored median, `None` if the stage has not been recorded on this input
+	pub baseline: Option<Duration>,
+	pub median: Duration,
+	/// How much slower the stage got, in percent of the baseline, negative if it got faster
+	pub change: Option<f64>,
+	/// Whether the change exceeds the threshold
+	pub regressed: bool,
+}
+
+/// Compare the medians of a day's stages with the baseline
+///
+/// `threshold` is the slowdown in percent a stage may have before it counts as regressed.
+pub fn compare(timings: &DayTimings, baseline: &Baseline, threshold: f64) -> Vec<Comparison> {
+	timings
+		.stages
+		.iter()
+		.map(|timing| {
+			let stored = baseline.get(timings.day, timing.stage, &timings.fingerprint);
+			let change = stored.map(|stored| {
+				let stored = stored.as_secs_f64().max(f64::MIN_POSITIVE);
+				(timing.stats.median.as_secs_f64() / stored - 1.0) * 100.0
+			});
+
+			Comparison {
+				day: timings.day,
+				stage: timing.stage,
+				baseline: stored,
+				median: timing.stats.median,
+				change,
+				regressed: change.is_some_and(|change| change > threshold),
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,82 @@
+//! Cooperative cancellation of long running solvers
+//!
+//! The runner gives every part a deadline with [`with_deadline`]. Solvers call [`checkpoint`] in
+//! their long running loops, which unwinds with [`Cancelled`] once the deadline has passed. The
+//! runner catches the unwind and reports the part as timed out.
+
+use std::{
+	cell::Cell,
+	panic,
+	time::{Duration, Instant},
+};
+
+/// How many checkpoints pass between two looks at the clock
+const CLOCK_INTERVAL: u32 = 1024;
+
+thread_local! {
+	static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+	static SKIPPED: Cell<u32> = const { Cell::new(0) };
+}
+
+/// The payload of the unwind started by [`checkpoint`] after the deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+/// Run `f` on this thread with a deadline for its checkpoints, or without one for `None`
+///
+/// `f` unwinds with [`Cancelled`] if it reaches a checkpoint after `timeout` has passed.
+pub fn with_deadline<R>(timeout: Option<Duration>, f: impl FnOnce() -> R) -> R {
+	/// Restores the previous deadline, also when `f` unwinds
+	struct Restore(Option<Instant>);
+
+	impl Drop for Restore {
+		fn drop(&mut self) {
+			DEADLINE.set(self.0);
+		}
+	}
+
+	let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+	let _restore = Restore(DEADLINE.replace(deadline));
+	SKIPPED.set(0);
+	f()
+}
+
+/// Stop the current solver if its deadline has passed
+///
+/// Cheap enough for inner loops: without a deadline this is a thread local read, and the clock
+/// is only read every 1024 calls.
+pub fn checkpoint() {
+	let Some(deadline) = DEADLINE.get() else {
+		return;
+	};
+
+	let skipped = SKIPPED.get() + 1;
+	if skipped < CLOCK_INTERVAL {
+		SKIPPED.set(skipped);
+		return;
+	}
+	SKIPPED.set(0);
+
+	if Instant::now() >= deadline {
+		// does not run the panic hook, so nothing is printed
+		panic::resume_unwind(Box::new(Cancelled));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unwinds_as_cancelled_after_the_deadline() {
+		let result = panic::catch_unwind(|| {
+			with_deadline(Some(Duration::ZERO), || loop {
+				checkpoint();
+			})
+		});
+
+		let payload = result.unwrap_err();
+		assert_eq!(payload.downcast_ref::<Cancelled>(), Some(&Cancelled));
+		assert_eq!(DEADLINE.get(), None);
+	}
+}
@@ -0,0 +1,774 @@
+use std::{path::PathBuf, time::Duration};
+
+use advent_of_code_2024::{
+	bench,
+	days::{self, Part},
+	fuzz::Fuzzing,
+	input::InputSource,
+	params::Overrides,
+	pool,
+	runner::Timeouts,
+	timing::Timing,
+	trace::Level,
+	verify, Error,
+};
+
+use crate::{report::Format, scaffold, watch::Watch};
+
+pub const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+  run <days>           Run the given days: a number (5), an inclusive range
+                       (1..9), a comma separated list (1,3,5) or `all`
+  watch <day>          Run the day and run it again whenever its input changes,
+                       until interrupted
+  verify [days]        Check the answers of the given days (default `all`)
+                       against the answers file
+  bench [days]         Time every stage of the given days (default `all`) and
+                       compare the medians with the baseline file
+  generate <day>       Write a random input for the day in the format of its
+                       puzzle input
+  diff [days]          Check the solutions of the given days (default `all`)
+                       against naive reference solutions on generated inputs
+  fuzz [days]          Feed the given days (default `all`) mutated inputs and
+                       report inputs they panic or hang on
+  params [days]        List the parameters of the given days (default `all`)
+  new-day <day>        Create src/days/dayNN with a solution template, an empty
+                       input and an empty example, and register the day
+  help                 Show this message
+
+Options for run:
+  --part <1|2>         Only run the given part
+  --input <path>       Read the input from a file, or from stdin if the path is `-`
+  --input-dir <dir>    Read the inputs from <dir>/dayNN/input or <dir>/dayNN.txt
+  --inputs <dir>       Solve a single day for every file in <dir> and print one
+                       row per file
+  --time               Time the parse stage and each part separately
+                       (builds with the `count-allocations` feature also report
+                       allocations and peak memory)
+  --runs <n>           Timed runs per stage (default 10, implies --time)
+  --warmup <n>         Untimed runs per stage before timing (default 1, implies --time)
+  --format <text|json> Print tables (default) or a JSON array of records
+  --param <name=value> Override a parameter of the solutions (repeatable), see
+                       `params` for the parameters of each day
+  --example            Solve the examples of the puzzle descriptions instead and
+                       check them against their published answers
+  --timeout [<day>[:<part>]=]<seconds>
+                       Give up on a part after this long and report it as timed
+                       out (repeatable, default: no limit); a limit for a day or
+                       a part overrides the one for all parts, e.g.
+                       `--timeout 2 --timeout 6:2=30`
+  --jobs <n>           Days solved concurrently (default: available parallelism,
+                       days are always solved one at a time with --time)
+  -v, -vv, --verbose   Print the steps of the solutions to stderr, -vv also the
+                       intermediate ones (cannot be combined with timing)
+
+Options for watch:
+  --part, --input, --input-dir, --param
+                       As for run
+  --runs <n>           Timed runs per stage (default 1)
+  --warmup <n>         Untimed runs per stage before timing (default 0)
+  --interval <ms>      Time between checks for changes (default 500)
+  --rebuild            Also watch src/days/dayNN and rebuild and run the day with
+                       cargo on every change
+
+Options for verify:
+  --input <path>, --input-dir <dir>
+                       As for run
+  --answers <path>     The answers file (default: answers.txt in this repository)
+  --record             Store the answers of parts without an expected answer
+
+Options for bench:
+  --input <path>, --input-dir <dir>, --runs <n>, --warmup <n>, --format <text|json>
+                       As for run
+  --threshold <percent>
+                       How much slower than its baseline a stage may get before
+                       it counts as regressed (default 10)
+  --baseline <path>    The baseline file (default: benchmarks.txt in this
+                       repository)
+  --record             Store the medians as the new baseline instead of failing
+                       on regressions
+
+Options for generate:
+  --seed <n>           Seed of the random input (default: based on the time);
+                       the same seed and size always give the same input
+  --size <n>           Size of the input in a unit of the day, such as lines or
+                       the grid width (default: about the size of a real input)
+  --output <path>      Write the input to a file instead of stdout
+
+Options for diff:
+  --part <1|2>         Only check the given part
+  --seeds <n>          Generated inputs per day (default 100), seeds 0 to n - 1
+                       with sizes cycling through the small sizes of the day
+  --param <name=value> As for run
+
+Options for fuzz:
+  --iterations <n>     Mutated inputs per day (default 10000)
+  --seed <n>           Seed of the mutations (default 0)
+  --time-limit <ms>    Time limit per input before it counts as hanging
+                       (default 5000); overflows are only caught by debug builds
+
+Options for new-day:
+  --root <dir>         The crate to create the day in (default: this repository)
+
+Without --input or --input-dir, inputs are read from the directory in
+AOC_INPUT_DIR, or from the inputs checked into this repository.";
+
+/// A parsed command line invocation
+#[derive(Debug)]
+pub enum Command {
+	Run {
+		days: Vec<u8>,
+		part: Option<Part>,
+		input: InputSource,
+		timing: Option<Timing>,
+		format: Format,
+		jobs: usize,
+		/// Directory of inputs to run a single day on, one after the other
+		batch: Option<PathBuf>,
+		params: Overrides,
+		/// Solve the embedded examples instead of the inputs
+		example: bool,
+		/// The time limits of the parts
+		timeouts: Timeouts,
+		/// The most detailed trace events to print, none by default
+		trace: Option<Level>,
+	},
+	Watch(Watch),
+	Verify {
+		days: Vec<u8>,
+		input: InputSource,
+		answers: PathBuf,
+		record: bool,
+	},
+	Generate {
+		day: u8,
+		seed: Option<u64>,
+		size: Option<usize>,
+		output: Option<PathBuf>,
+	},
+	Diff {
+		days: Vec<u8>,
+		part: Option<Part>,
+		seeds: u64,
+		params: Overrides,
+	},
+	Fuzz {
+		days: Vec<u8>,
+		fuzzing: Fuzzing,
+	},
+	Bench {
+		days: Vec<u8>,
+		input: InputSource,
+		timing: Timing,
+		format: Format,
+		/// The slowdown in percent a stage may have before it counts as regressed
+		threshold: f64,
+		baseline: PathBuf,
+		record: bool,
+	},
+	Params {
+		days: Vec<u8>,
+	},
+	NewDay {
+		day: u8,
+		root: PathBuf,
+	},
+	Help,
+}
+
+/// Parse the command line arguments (without the program name)
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, Error> {
+	parse_command(args.into_iter()).map_err(Error::Usage)
+}
+
+/// Parse the command and dispatch to the parser of its arguments
+fn parse_command(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let command = match args.next() {
+		Some(command) => command,
+		None => return Ok(Command::Help),
+	};
+
+	match command.as_str() {
+		"run" => parse_run(args),
+		"watch" => parse_watch(args),
+		"verify" => parse_verify(args),
+		"bench" => parse_bench(args),
+		"generate" => parse_generate(args),
+		"diff" => parse_diff(args),
+		"fuzz" => parse_fuzz(args),
+		"params" => parse_params(args),
+		"new-day" => parse_new_day(args),
+		"help" | "-h" | "--help" => Ok(Command::Help),
+		_ => Err(format!("unknown command `{}`", command)),
+	}
+}
+
+/// Parse the arguments of the `run` command
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+	let mut part = None;
+	let mut input = None;
+	let mut timing = None;
+	let mut format = Format::default();
+	let mut jobs = pool::default_threads();
+	let mut batch = None;
+	let mut params = Overrides::default();
+	let mut example = false;
+	let mut timeouts = Timeouts::default();
+	let mut verbosity = 0;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--part" | "-p" => {
+				let value = args.next().ok_or("`--part` expects a value")?;
+				part = Some(parse_part(&value)?);
+			}
+			"--input" | "-i" | "--input-dir" => input = Some(parse_input(&arg, &mut args)?),
+			"--inputs" => {
+				let value = args.next().ok_or("`--inputs` expects a directory")?;
+				batch = Some(PathBuf::from(value));
+			}
+			"--time" => {
+				timing.get_or_insert_with(Timing::default);
+			}
+			"--runs" => {
+				let value = args.next().ok_or("`--runs` expects a number")?;
+				timing.get_or_insert_with(Timing::default).runs = parse_count(&value)?;
+			}
+			"--warmup" => {
+				let value = args.next().ok_or("`--warmup` expects a number")?;
+				timing.get_or_insert_with(Timing::default).warmup = parse_count(&value)?;
+			}
+			"--format" => {
+				let value = args.next().ok_or("`--format` expects a format")?;
+				format = parse_format(&value)?;
+			}
+			"--param" => {
+				let value = args.next().ok_or("`--param` expects name=value")?;
+				params.add(&value)?;
+			}
+			"--example" => example = true,
+			"--timeout" => {
+				let value = args.next().ok_or("`--timeout` expects seconds")?;
+				timeouts.add(&value)?;
+			}
+			"--jobs" | "-j" => {
+				let value = args.next().ok_or("`--jobs` expects a number")?;
+				jobs = parse_count(&value)?.max(1);
+			}
+			"--verbose" | "-v" => verbosity += 1,
+			"-vv" => verbosity += 2,
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	let days = days.ok_or("`run` expects the days to run")?;
+	if batch.is_some() {
+		if days.len() > 1 {
+			return Err("`--inputs` can only be used with a single day".to_string());
+		}
+		if input.is_some() {
+			return Err(
+				"`--inputs` cannot be combined with `--input` or `--input-dir`".to_string(),
+			);
+		}
+		if timing.is_some() {
+			return Err("`--inputs` cannot be combined with timing".to_string());
+		}
+	}
+	if example {
+		if input.is_some() || batch.is_some() {
+			return Err("`--example` cannot be combined with other inputs".to_string());
+		}
+		if timing.is_some() {
+			return Err("`--example` cannot be combined with timing".to_string());
+		}
+		if params.names().next().is_some() {
+			return Err(
+				"`--example` checks the published answers, which need the default parameters"
+					.to_string(),
+			);
+		}
+	}
+	if !timeouts.is_empty() && (timing.is_some() || example) {
+		return Err("`--timeout` cannot be combined with timing or `--example`".to_string());
+	}
+	let trace = match verbosity {
+		0 => None,
+		1 => Some(Level::Info),
+		_ => Some(Level::Debug),
+	};
+	if trace.is_some() && timing.is_some() {
+		// every timed run would print its events again, and printing them skews the timings
+		return Err("`--verbose` cannot be combined with timing".to_string());
+	}
+	let input = check_input(&days, input)?;
+
+	Ok(Command::Run {
+		days,
+		part,
+		input,
+		timing,
+		format,
+		jobs,
+		batch,
+		params,
+		example,
+		timeouts,
+		trace,
+	})
+}
+
+/// Parse the arguments of the `watch` command
+fn parse_watch(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut day = None;
+	let mut part = None;
+	let mut input = None;
+	let mut params = Overrides::default();
+	let mut timing = Timing { warmup: 0, runs: 1 };
+	let mut interval = Duration::from_millis(500);
+	let mut rebuild = false;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--part" | "-p" => {
+				let value = args.next().ok_or("`--part` expects a value")?;
+				part = Some(parse_part(&value)?);
+			}
+			"--input" | "-i" | "--input-dir" => input = Some(parse_input(&arg, &mut args)?),
+			"--param" => {
+				let value = args.next().ok_or("`--param` expects name=value")?;
+				params.add(&value)?;
+			}
+			"--runs" => {
+				let value = args.next().ok_or("`--runs` expects a number")?;
+				timing.runs = parse_count(&value)?;
+			}
+			"--warmup" => {
+				let value = args.next().ok_or("`--warmup` expects a number")?;
+				timing.warmup = parse_count(&value)?;
+			}
+			"--interval" => {
+				let value = args.next().ok_or("`--interval` expects milliseconds")?;
+				interval = Duration::from_millis(parse_count(&value)? as u64);
+			}
+			"--rebuild" => rebuild = true,
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if day.is_none() => day = Some(parse_day(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	let day = day.ok_or("`watch` expects the day to watch")?;
+	let input = input.unwrap_or_default();
+	if input == InputSource::Stdin {
+		return Err("`watch` cannot watch stdin".to_string());
+	}
+
+	Ok(Command::Watch(Watch {
+		day,
+		part,
+		input,
+		params,
+		timing,
+		interval,
+		rebuild,
+		root: scaffold::default_root(),
+	}))
+}
+
+/// Parse the arguments of the `verify` command
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+	let mut input = None;
+	let mut answers = None;
+	let mut record = false;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--input" | "-i" | "--input-dir" => input = Some(parse_input(&arg, &mut args)?),
+			"--answers" => {
+				let value = args.next().ok_or("`--answers` expects a path")?;
+				answers = Some(PathBuf::from(value));
+			}
+			"--record" => record = true,
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	let days = days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect());
+	let input = check_input(&days, input)?;
+
+	Ok(Command::Verify {
+		days,
+		input,
+		answers: answers.unwrap_or_else(verify::default_path),
+		record,
+	})
+}
+
+/// Parse the arguments of the `bench` command
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+	let mut input = None;
+	let mut timing = Timing::default();
+	let mut format = Format::default();
+	let mut threshold = 10.0;
+	let mut baseline = None;
+	let mut record = false;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--input" | "-i" | "--input-dir" => input = Some(parse_input(&arg, &mut args)?),
+			"--runs" => {
+				let value = args.next().ok_or("`--runs` expects a number")?;
+				timing.runs = parse_count(&value)?;
+			}
+			"--warmup" => {
+				let value = args.next().ok_or("`--warmup` expects a number")?;
+				timing.warmup = parse_count(&value)?;
+			}
+			"--format" => {
+				let value = args.next().ok_or("`--format` expects a format")?;
+				format = parse_format(&value)?;
+			}
+			"--threshold" => {
+				let value = args.next().ok_or("`--threshold` expects a percentage")?;
+				threshold = value
+					.trim_end_matches('%')
+					.parse::<f64>()
+					.ok()
+					.filter(|threshold| threshold.is_finite() && *threshold >= 0.0)
+					.ok_or_else(|| format!("invalid threshold `{}`", value))?;
+			}
+			"--baseline" => {
+				let value = args.next().ok_or("`--baseline` expects a path")?;
+				baseline = Some(PathBuf::from(value));
+			}
+			"--record" => record = true,
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	let days = days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect());
+	let input = check_input(&days, input)?;
+
+	Ok(Command::Bench {
+		days,
+		input,
+		timing,
+		format,
+		threshold,
+		baseline: baseline.unwrap_or_else(bench::default_path),
+		record,
+	})
+}
+
+/// Parse the arguments of the `generate` command
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut day = None;
+	let mut seed = None;
+	let mut size = None;
+	let mut output = None;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--seed" => {
+				let value = args.next().ok_or("`--seed` expects a number")?;
+				seed = Some(
+					value
+						.parse()
+						.map_err(|_| format!("invalid seed `{}`", value))?,
+				);
+			}
+			"--size" => {
+				let value = args.next().ok_or("`--size` expects a number")?;
+				let value = parse_count(&value)?;
+				if value == 0 {
+					return Err("`--size` has to be at least 1".to_string());
+				}
+				size = Some(value);
+			}
+			"--output" | "-o" => {
+				let value = args.next().ok_or("`--output` expects a path")?;
+				output = Some(PathBuf::from(value));
+			}
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if day.is_none() => day = Some(parse_day(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	Ok(Command::Generate {
+		day: day.ok_or("`generate` expects the day to generate an input for")?,
+		seed,
+		size,
+		output,
+	})
+}
+
+/// Parse the arguments of the `diff` command
+fn parse_diff(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+	let mut part = None;
+	let mut seeds = 100;
+	let mut params = Overrides::default();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--part" | "-p" => {
+				let value = args.next().ok_or("`--part` expects a value")?;
+				part = Some(parse_part(&value)?);
+			}
+			"--seeds" => {
+				let value = args.next().ok_or("`--seeds` expects a number")?;
+				seeds = parse_count(&value)? as u64;
+			}
+			"--param" => {
+				let value = args.next().ok_or("`--param` expects name=value")?;
+				params.add(&value)?;
+			}
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	Ok(Command::Diff {
+		days: days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect()),
+		part,
+		seeds,
+		params,
+	})
+}
+
+/// Parse the arguments of the `fuzz` command
+fn parse_fuzz(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+	let mut fuzzing = Fuzzing::default();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--iterations" => {
+				let value = args.next().ok_or("`--iterations` expects a number")?;
+				fuzzing.iterations = parse_count(&value)?;
+			}
+			"--seed" => {
+				let value = args.next().ok_or("`--seed` expects a number")?;
+				fuzzing.seed = value
+					.parse()
+					.map_err(|_| format!("invalid seed `{}`", value))?;
+			}
+			"--time-limit" => {
+				let value = args.next().ok_or("`--time-limit` expects milliseconds")?;
+				fuzzing.time_limit = Duration::from_millis(parse_count(&value)? as u64);
+			}
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	Ok(Command::Fuzz {
+		days: days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect()),
+		fuzzing,
+	})
+}
+
+/// Parse the arguments of the `params` command
+fn parse_params(args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut days = None;
+
+	for arg in args {
+		match arg.as_str() {
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if days.is_none() => days = Some(parse_days(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	Ok(Command::Params {
+		days: days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect()),
+	})
+}
+
+/// Parse the arguments of the `new-day` command
+fn parse_new_day(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let mut day = None;
+	let mut root = None;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--root" => {
+				let value = args.next().ok_or("`--root` expects a directory")?;
+				root = Some(PathBuf::from(value));
+			}
+			_ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+			_ if day.is_none() => day = Some(parse_day(&arg)?),
+			_ => return Err(format!("unexpected argument `{}`", arg)),
+		}
+	}
+
+	Ok(Command::NewDay {
+		day: day.ok_or("`new-day` expects the day to create")?,
+		root: root.unwrap_or_else(scaffold::default_root),
+	})
+}
+
+/// Parse the value of `--input` or `--input-dir`
+fn parse_input(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<InputSource, String> {
+	let value = args
+		.next()
+		.ok_or_else(|| format!("`{}` expects a path", flag))?;
+
+	Ok(match (flag, value.as_str()) {
+		("--input-dir", _) => InputSource::Directory(PathBuf::from(value)),
+		(_, "-") => InputSource::Stdin,
+		_ => InputSource::File(PathBuf::from(value)),
+	})
+}
+
+/// Use the default input source if none is given, and reject a single input for several days
+fn check_input(days: &[u8], input: Option<InputSource>) -> Result<InputSource, String> {
+	let input = input.unwrap_or_default();
+	if days.len() > 1 && !matches!(input, InputSource::Directory(_)) {
+		return Err("a single input can only be used with a single day".to_string());
+	}
+	Ok(input)
+}
+
+/// Parse a part number
+fn parse_part(value: &str) -> Result<Part, String> {
+	match value {
+		"1" => Ok(Part::One),
+		"2" => Ok(Part::Two),
+		_ => Err(format!("invalid part `{}`, expected 1 or 2", value)),
+	}
+}
+
+/// Parse an output format
+fn parse_format(value: &str) -> Result<Format, String> {
+	match value {
+		"text" => Ok(Format::Text),
+		"json" => Ok(Format::Json),
+		_ => Err(format!("invalid format `{}`, expected text or json", value)),
+	}
+}
+
+/// Parse a repetition count
+fn parse_count(value: &str) -> Result<usize, String> {
+	value
+		.parse()
+		.map_err(|_| format!("invalid count `{}`", value))
+}
+
+/// Parse a day selection: `all`, a single day, an inclusive range or a comma separated list
+fn parse_days(value: &str) -> Result<Vec<u8>, String> {
+	if value == "all" {
+		return Ok(days::DAYS.iter().map(|day| day.number).collect());
+	}
+
+	let mut selected = Vec::new();
+	for item in value.split(',') {
+		if let Some((start, end)) = item.split_once("..") {
+			let start = parse_day(start)?;
+			let end = parse_day(end.strip_prefix('=').unwrap_or(end))?;
+			if start > end {
+				return Err(format!("empty day range `{}`", item));
+			}
+			selected.extend(start..=end);
+		} else {
+			selected.push(parse_day(item)?);
+		}
+	}
+
+	Ok(selected)
+}
+
+/// Parse a single day number
+fn parse_day(value: &str) -> Result<u8, String> {
+	value
+		.parse()
+		.map_err(|_| format!("invalid day `{}`", value))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Parse the arguments of `aoc run`
+	fn run(args: &str) -> Result<Command, String> {
+		parse_run(args.split(' ').map(str::to_string))
+	}
+
+	#[test]
+	fn parses_day_selections() {
+		let all = days::DAYS.iter().map(|day| day.number).collect::<Vec<_>>();
+		assert_eq!(parse_days("all"), Ok(all));
+		assert_eq!(parse_days("7"), Ok(vec![7]));
+		assert_eq!(parse_days("1..9"), Ok((1..=9).collect()));
+		assert_eq!(parse_days("1..=9"), Ok((1..=9).collect()));
+		assert_eq!(parse_days("1,3,5"), Ok(vec![1, 3, 5]));
+		assert_eq!(parse_days("1,4..5,9"), Ok(vec![1, 4, 5, 9]));
+	}
+
+	#[test]
+	fn rejects_invalid_day_selections() {
+		assert_eq!(
+			parse_days("5..3"),
+			Err("empty day range `5..3`".to_string())
+		);
+		assert_eq!(parse_days("1,x"), Err("invalid day `x`".to_string()));
+		assert_eq!(parse_days("1.."), Err("invalid day ``".to_string()));
+		assert!(parse_days("256").is_err());
+	}
+
+	#[test]
+	fn parses_run_options() {
+		let Ok(Command::Run {
+			days, part, trace, ..
+		}) = run("1..3 --part 2 -v")
+		else {
+			panic!("`run` was rejected");
+		};
+		assert_eq!(days, [1, 2, 3]);
+		assert_eq!(part, Some(Part::Two));
+		assert_eq!(trace, Some(Level::Info));
+	}
+
+	#[test]
+	fn rejects_invalid_parts() {
+		assert_eq!(
+			run("1 --part 3").unwrap_err(),
+			"invalid part `3`, expected 1 or 2"
+		);
+		assert_eq!(run("1 --part").unwrap_err(), "`--part` expects a value");
+	}
+
+	#[test]
+	fn rejects_conflicting_run_options() {
+		assert_eq!(
+			run("1,2 --inputs inputs").unwrap_err(),
+			"`--inputs` can only be used with a single day"
+		);
+		assert!(run("1 --example --param word=XMAS")
+			.unwrap_err()
+			.starts_with("`--example` checks the published answers"));
+		assert_eq!(
+			run("1 --verbose --time").unwrap_err(),
+			"`--verbose` cannot be combined with timing"
+		);
+		assert_eq!(
+			run("1,2 --input day01.txt").unwrap_err(),
+			"a single input can only be used with a single day"
+		);
+	}
+}
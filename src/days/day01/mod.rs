@@ -1,48 +1,100 @@
-use std::{cmp::Ordering, fs};
+mod reference;
+
+use std::cmp::Ordering;
+
+use crate::{
+	cancel,
+	error::ParseError,
+	rng::Rng,
+	scan::Scanner,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+	type Input = (Vec<usize>, Vec<usize>);
+	type PartOne = u128;
+	type PartTwo = u128;
+	type Params = ();
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("11"),
+		part_two: Some("31"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "lines",
+		default: 1000,
+		small: 20,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		convert_input(input.as_bytes())
+	}
+
+	fn part_one((vec1, vec2): &Self::Input, _params: &()) -> u128 {
+		distance(vec1, vec2)
+	}
 
-pub fn main() {
-	let content = read_input();
-	let (vec1, vec2) = convert_input(content);
-	let distance = distance(&vec1, &vec2);
-	let similarity = similarity(&vec1, &vec2);
+	fn part_two((vec1, vec2): &Self::Input, _params: &()) -> u128 {
+		similarity(vec1, vec2)
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_lists(rng, size)
+	}
+
+	fn reference_one(input: &str, _params: &()) -> u128 {
+		reference::part_one(input)
+	}
+
+	fn reference_two(input: &str, _params: &()) -> u128 {
+		reference::part_two(input)
+	}
+}
 
-	println!("The distance between the two vectors is: {}", distance);
-	println!("The similarity between the two vectors is: {}", similarity);
+/// Total distance between the sorted left and right location lists
+pub fn part_one(input: &str) -> Result<u128, ParseError> {
+	Ok(Day01::part_one(&Day01::parse_input(input)?, &()))
 }
 
-/// Read the input file to bytes
-fn read_input() -> Vec<u8> {
-	// format: 5 digits, 3 spaces, 5 digits
-	fs::read("./src/days/day01/input").expect("Unable to read file.")
+/// Similarity score of the left and right location lists
+pub fn part_two(input: &str) -> Result<u128, ParseError> {
+	Ok(Day01::part_two(&Day01::parse_input(input)?, &()))
 }
 
 /// Parse the input columns to two sorted vectors
-fn convert_input(content: Vec<u8>) -> (Vec<usize>, Vec<usize>) {
+///
+/// format: a number, spaces, a number
+fn convert_input(content: &[u8]) -> Result<(Vec<usize>, Vec<usize>), ParseError> {
 	let mut vec1 = Vec::new();
 	let mut vec2 = Vec::new();
 
-	let mut index = 0;
-	while index < content.len() {
-		// if newline, skip
-		if content[index] == b'\n' {
-			index += 1;
-			continue;
+	let mut scanner = Scanner::new(content);
+	loop {
+		// skip empty lines
+		scanner.skip(b"\n");
+		if scanner.is_empty() {
+			break;
 		}
 
-		// first 5 bytes are first number
-		let num1 = fast_conversion(&content[index..(index + 5)]);
-		insert_sorted(&mut vec1, num1);
+		let left = scanner.integer()?;
 
-		index += 5 + 3;
+		if scanner.skip(b" ") == 0 {
+			return Err(scanner.error("a space"));
+		}
 
-		// next 5 bytes are second number
-		let num2 = fast_conversion(&content[index..(index + 5)]);
-		insert_sorted(&mut vec2, num2);
+		let right = scanner.integer()?;
+		scanner.expect_line_end()?;
 
-		index += 5;
+		insert_sorted(&mut vec1, left);
+		insert_sorted(&mut vec2, right);
 	}
 
-	(vec1, vec2)
+	Ok((vec1, vec2))
 }
 
 /// This function inserts a value into a sorted vector
@@ -51,38 +103,34 @@ fn insert_sorted(vec: &mut Vec<usize>, value: usize) {
 	vec.insert(pos, value);
 }
 
-/// This function is a fast conversion from a sequence of digit bytes (utf8) to an integer
-fn fast_conversion(bytes: &[u8]) -> usize {
-	let mut result = 0;
-	for byte in bytes {
-		result = result * 10 + (byte - b'0') as usize;
-	}
-	result
-}
-
 /// Determine the distance between two vectors
-fn distance(vec1: &Vec<usize>, vec2: &Vec<usize>) -> usize {
+///
+/// Computed in `u128`, as the distances of 64 bit ids can add up to more than 64 bits.
+fn distance(vec1: &[usize], vec2: &[usize]) -> u128 {
 	let mut distance = 0;
 
 	for i in 0..vec1.len() {
+		cancel::checkpoint();
+		trace!(Debug, "pair", left = vec1[i], right = vec2[i]);
 		distance += match vec1[i].cmp(&vec2[i]) {
 			Ordering::Less => vec2[i] - vec1[i],
 			Ordering::Greater => vec1[i] - vec2[i],
 			Ordering::Equal => 0,
-		}
+		} as u128
 	}
 
 	distance
 }
 
-/// Determine the similarity between two vectors
-fn similarity(vec1: &Vec<usize>, vec2: &Vec<usize>) -> usize {
+/// Determine the similarity between two vectors, in `u128` like the distance
+fn similarity(vec1: &[usize], vec2: &[usize]) -> u128 {
 	let mut similarity = 0;
 
 	let mut left_index = 0;
 	let mut right_index = 0;
 
 	while left_index < vec1.len() && right_index < vec2.len() {
+		cancel::checkpoint();
 		if vec1[left_index] == 0 {
 			left_index += 1;
 			continue;
@@ -92,17 +140,40 @@ fn similarity(vec1: &Vec<usize>, vec2: &Vec<usize>) -> usize {
 			Ordering::Less => left_index += 1,
 			Ordering::Greater => right_index += 1,
 			Ordering::Equal => {
+				let value = vec1[left_index];
 				right_index += 1;
 				let mut count = 1;
-				while right_index < vec2.len() && vec1[left_index] == vec2[right_index] {
+				while right_index < vec2.len() && value == vec2[right_index] {
 					count += 1;
 					right_index += 1;
 				}
-				similarity += vec1[left_index] * count;
-				left_index += 1;
+				trace!(Info, "similar", value = value, count = count);
+				// repeated values on the left all score the same count
+				while left_index < vec1.len() && vec1[left_index] == value {
+					similarity += value as u128 * count;
+					left_index += 1;
+				}
 			}
 		}
 	}
 
 	similarity
 }
+
+/// Generate `lines` pairs of 5 digit location ids, some of which appear in both lists
+fn generate_lists(rng: &mut Rng, lines: usize) -> String {
+	let mut left = Vec::with_capacity(lines);
+	let mut content = String::new();
+
+	for _ in 0..lines {
+		left.push(rng.range(10000..=99999));
+		let right = if rng.chance(1, 3) {
+			*rng.choose(&left)
+		} else {
+			rng.range(10000..=99999)
+		};
+		content.push_str(&format!("{}   {}\n", left[left.len() - 1], right));
+	}
+
+	content
+}
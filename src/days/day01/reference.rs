@@ -0,0 +1,34 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+/// The left and right lists of the input
+fn lists(input: &str) -> (Vec<usize>, Vec<usize>) {
+	input
+		.lines()
+		.filter(|line| !line.is_empty())
+		.map(|line| {
+			let mut numbers = line
+				.split_whitespace()
+				.map(|number| number.parse::<usize>().unwrap());
+			(numbers.next().unwrap(), numbers.next().unwrap())
+		})
+		.unzip()
+}
+
+/// Pair the sorted lists and add up the differences
+pub fn part_one(input: &str) -> u128 {
+	let (mut left, mut right) = lists(input);
+	left.sort();
+	right.sort();
+	left.iter()
+		.zip(&right)
+		.map(|(left, right)| left.abs_diff(*right) as u128)
+		.sum()
+}
+
+/// Count the occurrences in the right list for every number of the left list
+pub fn part_two(input: &str) -> u128 {
+	let (left, right) = lists(input);
+	left.iter()
+		.map(|left| *left as u128 * right.iter().filter(|right| *right == left).count() as u128)
+		.sum()
+}
@@ -1,47 +1,147 @@
-use std::fs;
+mod reference;
+
+use crate::{
+	cancel,
+	error::ParseError,
+	params::{Param, Parameters, Values},
+	rng::Rng,
+	scan::Scanner,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
+
+/// The allowed differences between adjacent levels of a safe report
+pub struct StepBounds {
+	min: usize,
+	max: usize,
+}
+
+impl Parameters for StepBounds {
+	const DECLARED: &'static [Param] = &[
+		Param {
+			name: "min_step",
+			default: "1",
+			description: "Smallest difference between adjacent levels of a safe report",
+		},
+		Param {
+			name: "max_step",
+			default: "3",
+			description: "Largest difference between adjacent levels of a safe report",
+		},
+	];
+
+	fn from_values(values: &Values) -> Result<Self, String> {
+		Ok(StepBounds {
+			min: values.get("min_step")?,
+			max: values.get("max_step")?,
+		})
+	}
+}
+
+pub struct Day02;
 
-pub fn main() {
-	let content = read_input();
-	let safe_count = count_safe_reports(&content);
-	let dampened_safe_count = count_dampened_safe_reports(&content);
+impl Solution for Day02 {
+	type Input = Vec<Vec<usize>>;
+	type PartOne = usize;
+	type PartTwo = usize;
+	type Params = StepBounds;
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("2"),
+		part_two: Some("4"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "reports",
+		default: 1000,
+		small: 20,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		read_reports(input)
+	}
+
+	fn part_one(reports: &Self::Input, bounds: &StepBounds) -> usize {
+		count_safe_reports(reports, bounds)
+	}
+
+	fn part_two(reports: &Self::Input, bounds: &StepBounds) -> usize {
+		count_dampened_safe_reports(reports, bounds)
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_reports(rng, size)
+	}
+
+	fn reference_one(input: &str, bounds: &StepBounds) -> usize {
+		reference::part_one(input, bounds)
+	}
 
-	println!("Safe reports: {}", safe_count);
-	println!("Dampened safe reports: {}", dampened_safe_count);
+	fn reference_two(input: &str, bounds: &StepBounds) -> usize {
+		reference::part_two(input, bounds)
+	}
 }
 
-/// Read the input file to bytes
-fn read_input() -> String {
-	fs::read_to_string("./src/days/day02/input").expect("Unable to read file.")
+/// Number of safe reports
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+	Ok(Day02::part_one(
+		&Day02::parse_input(input)?,
+		&StepBounds::defaults(),
+	))
 }
 
-fn count_safe_reports(content: &String) -> usize {
-	let mut count = 0;
+/// Number of reports that are safe after removing at most one level
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+	Ok(Day02::part_two(
+		&Day02::parse_input(input)?,
+		&StepBounds::defaults(),
+	))
+}
+
+/// Parse one report of whitespace separated levels per line
+fn read_reports(content: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+	let mut reports = Vec::new();
+
+	let mut scanner = Scanner::new(content.as_bytes());
+	while !scanner.is_empty() {
+		let mut report = Vec::new();
+		scanner.skip(b" \t");
+		while !scanner.at_line_end() {
+			report.push(scanner.integer()?);
+			scanner.skip(b" \t");
+		}
+		scanner.expect_line_end()?;
+		reports.push(report);
+	}
+
+	Ok(reports)
+}
 
-	for line in content.lines() {
-		let report = line
-			.split_whitespace()
-			.map(|x| x.parse::<usize>().unwrap())
-			.collect::<Vec<usize>>();
+fn count_safe_reports(reports: &[Vec<usize>], bounds: &StepBounds) -> usize {
+	let mut count = 0;
 
-		if is_report_safe(&report) {
+	for report in reports {
+		cancel::checkpoint();
+		if is_report_safe(report, bounds) {
 			count += 1;
+		} else {
+			trace!(Info, "unsafe", report = report);
 		}
 	}
 
 	count
 }
 
-fn count_dampened_safe_reports(content: &String) -> usize {
+fn count_dampened_safe_reports(reports: &[Vec<usize>], bounds: &StepBounds) -> usize {
 	let mut count = 0;
 
-	for line in content.lines() {
-		let mut report = line
-			.split_whitespace()
-			.map(|x| x.parse::<usize>().unwrap())
-			.collect::<Vec<usize>>();
-
-		if is_report_safe_dampened(&mut report) {
+	for report in reports {
+		cancel::checkpoint();
+		if is_report_safe_dampened(&mut report.clone(), bounds) {
 			count += 1;
+		} else {
+			trace!(Info, "unsafe", report = report);
 		}
 	}
 
@@ -50,8 +150,8 @@ fn count_dampened_safe_reports(content: &String) -> usize {
 
 /// a report only counts as safe if both of the following are true:
 /// - the levels are either all increasing or all decreasing
-/// - any two adjacent levels differ by at least one and at most three
-fn is_report_safe(report: &Vec<usize>) -> bool {
+/// - any two adjacent levels differ by at least `min_step` and at most `max_step`
+fn is_report_safe(report: &[usize], bounds: &StepBounds) -> bool {
 	if report.len() <= 1 {
 		return true;
 	}
@@ -65,7 +165,7 @@ fn is_report_safe(report: &Vec<usize>) -> bool {
 				return false;
 			}
 			let diff = report[i + 1] - report[i];
-			if diff < 1 || diff > 3 {
+			if !(bounds.min..=bounds.max).contains(&diff) {
 				return false;
 			}
 		}
@@ -75,7 +175,7 @@ fn is_report_safe(report: &Vec<usize>) -> bool {
 				return false;
 			}
 			let diff = report[i] - report[i + 1];
-			if diff < 1 || diff > 3 {
+			if !(bounds.min..=bounds.max).contains(&diff) {
 				return false;
 			}
 		}
@@ -85,19 +185,20 @@ fn is_report_safe(report: &Vec<usize>) -> bool {
 }
 
 /// a report only counts as dampened safe if removing at most one level from the report makes it safe
-fn is_report_safe_dampened(report: &mut Vec<usize>) -> bool {
+fn is_report_safe_dampened(report: &mut Vec<usize>, bounds: &StepBounds) -> bool {
 	if report.len() <= 1 {
 		return true;
 	}
 
-	if is_report_safe(&report) {
+	if is_report_safe(report, bounds) {
 		return true;
 	}
 
 	for i in 0..report.len() {
 		let removed = report.remove(i);
 
-		if is_report_safe(report) {
+		if is_report_safe(report, bounds) {
+			trace!(Debug, "dampened", index = i, level = removed);
 			return true;
 		}
 
@@ -106,3 +207,34 @@ fn is_report_safe_dampened(report: &mut Vec<usize>) -> bool {
 
 	false
 }
+
+/// Generate mostly monotonic reports, some of which break the rules once or more
+fn generate_reports(rng: &mut Rng, reports: usize) -> String {
+	let mut content = String::new();
+
+	for _ in 0..reports {
+		let increasing = rng.chance(1, 2);
+		let mut level = rng.range(30..=70);
+		let mut levels = vec![level.to_string()];
+
+		for _ in 1..rng.range(5..=8) {
+			// every tenth step is out of bounds or in the wrong direction
+			let step = if rng.chance(1, 10) {
+				rng.range(0..=6)
+			} else {
+				rng.range(1..=3)
+			};
+			level = if increasing == rng.chance(19, 20) {
+				level + step
+			} else {
+				level.saturating_sub(step)
+			};
+			levels.push(level.to_string());
+		}
+
+		content.push_str(&levels.join(" "));
+		content.push('\n');
+	}
+
+	content
+}
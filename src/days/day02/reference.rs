@@ -0,0 +1,46 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use super::StepBounds;
+
+/// The levels of every report
+fn reports(input: &str) -> Vec<Vec<usize>> {
+	input
+		.lines()
+		.map(|line| {
+			line.split_whitespace()
+				.map(|level| level.parse().unwrap())
+				.collect()
+		})
+		.collect()
+}
+
+/// Whether all steps go the same way and are within the bounds
+fn is_safe(report: &[usize], bounds: &StepBounds) -> bool {
+	let within = |a: usize, b: usize| a < b && (bounds.min..=bounds.max).contains(&(b - a));
+	let increasing = report.windows(2).all(|pair| within(pair[0], pair[1]));
+	let decreasing = report.windows(2).all(|pair| within(pair[1], pair[0]));
+	increasing || decreasing
+}
+
+/// Check every report
+pub fn part_one(input: &str, bounds: &StepBounds) -> usize {
+	reports(input)
+		.iter()
+		.filter(|report| is_safe(report, bounds))
+		.count()
+}
+
+/// Check every report, and every report with one level removed
+pub fn part_two(input: &str, bounds: &StepBounds) -> usize {
+	reports(input)
+		.iter()
+		.filter(|report| {
+			is_safe(report, bounds)
+				|| (0..report.len()).any(|removed| {
+					let mut dampened = report.to_vec();
+					dampened.remove(removed);
+					is_safe(&dampened, bounds)
+				})
+		})
+		.count()
+}
@@ -1,56 +1,172 @@
+mod reference;
+
 use regex::Regex;
-use std::fs;
 
-pub fn main() {
-	let content = read_input();
-	let sum = extract_mul_and_sum(&content);
-	let sum_with_conditions = extract_and_sum_with_conditions(&content);
+use crate::{
+	cancel,
+	error::ParseError,
+	rng::Rng,
+	scan,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
 
-	println!("Sum: {}", sum);
-	println!("Sum with conditions: {}", sum_with_conditions);
+/// An instruction found in the corrupted memory
+pub enum Instruction {
+	Do,
+	Dont,
+	Mul(usize, usize),
 }
 
-/// Read the input file to bytes
-fn read_input() -> String {
-	fs::read_to_string("./src/days/day03/input").expect("Unable to read file.")
+pub struct Day03;
+
+impl Solution for Day03 {
+	type Input = Vec<Instruction>;
+	type PartOne = usize;
+	type PartTwo = usize;
+	type Params = ();
+
+	const EXAMPLES: &'static [Example] = &[
+		Example {
+			input: include_str!("example"),
+			part_one: Some("161"),
+			part_two: None,
+		},
+		Example {
+			input: include_str!("example2"),
+			part_one: None,
+			part_two: Some("48"),
+		},
+	];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "characters",
+		default: 18000,
+		small: 300,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		extract_instructions(input)
+	}
+
+	fn part_one(instructions: &Self::Input, _params: &()) -> usize {
+		sum_muls(instructions)
+	}
+
+	fn part_two(instructions: &Self::Input, _params: &()) -> usize {
+		sum_muls_with_conditions(instructions)
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_memory(rng, size)
+	}
+
+	fn reference_one(input: &str, _params: &()) -> usize {
+		reference::part_one(input)
+	}
+
+	fn reference_two(input: &str, _params: &()) -> usize {
+		reference::part_two(input)
+	}
 }
 
-/// Extract the values from the input string
-fn extract_mul_and_sum(input: &String) -> usize {
-	let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-	let mut sum = 0;
+/// Sum of all `mul` instructions
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+	Ok(Day03::part_one(&Day03::parse_input(input)?, &()))
+}
+
+/// Sum of the `mul` instructions enabled by `do()` and `don't()`
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+	Ok(Day03::part_two(&Day03::parse_input(input)?, &()))
+}
+
+/// Extract the instructions from the input string
+fn extract_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+	// Match `do()`, `don't()`, and valid `mul(X,Y)` instructions
+	let re = Regex::new(r"do\(\)|don't\(\)|mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
+
+	let mut instructions = Vec::new();
 
 	for cap in re.captures_iter(input) {
-		let x: usize = cap[1].parse().unwrap();
-		let y: usize = cap[2].parse().unwrap();
+		if &cap[0] == "do()" {
+			instructions.push(Instruction::Do);
+		} else if &cap[0] == "don't()" {
+			instructions.push(Instruction::Dont);
+		} else {
+			let x = scan::integer_token(input, &cap[1])?;
+			let y = scan::integer_token(input, &cap[2])?;
+			instructions.push(Instruction::Mul(x, y));
+		}
+	}
+
+	Ok(instructions)
+}
 
-		sum += x * y;
+/// Sum all multiplications
+fn sum_muls(instructions: &[Instruction]) -> usize {
+	let mut sum = 0;
+
+	for instruction in instructions {
+		cancel::checkpoint();
+		if let Instruction::Mul(x, y) = instruction {
+			trace!(Info, "mul", x = x, y = y);
+			sum += x * y;
+		}
 	}
 
 	sum
 }
 
-/// Extract values and sum with conditions
-fn extract_and_sum_with_conditions(input: &String) -> usize {
-	// Match `do()`, `don't()`, and valid `mul(X,Y)` instructions
-	let re = Regex::new(r"do\(\)|don't\(\)|mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-
+/// Sum the multiplications with conditions
+fn sum_muls_with_conditions(instructions: &[Instruction]) -> usize {
 	let mut sum = 0;
 	let mut enabled = true; // Start with `mul` instructions enabled
 
-	for cap in re.captures_iter(input) {
-		if let Some(do_instruction) = cap.get(0).filter(|m| m.as_str() == "do()") {
-			enabled = true; // Enable future `mul` instructions
-		} else if let Some(dont_instruction) = cap.get(0).filter(|m| m.as_str() == "don't()") {
-			enabled = false; // Disable future `mul` instructions
-		} else if let (Some(x_match), Some(y_match)) = (cap.get(1), cap.get(2)) {
-			if enabled {
-				let x: usize = x_match.as_str().parse().unwrap();
-				let y: usize = y_match.as_str().parse().unwrap();
-				sum += x * y; // Add result of the multiplication
+	for instruction in instructions {
+		cancel::checkpoint();
+		match instruction {
+			Instruction::Do => enabled = true, // Enable future `mul` instructions
+			Instruction::Dont => enabled = false, // Disable future `mul` instructions
+			Instruction::Mul(x, y) => {
+				if enabled {
+					trace!(Info, "mul", x = x, y = y);
+					sum += x * y; // Add result of the multiplication
+				} else {
+					trace!(Debug, "disabled mul", x = x, y = y);
+				}
 			}
 		}
 	}
 
 	sum
 }
+
+/// Generate corrupted memory of about `characters` characters, in lines of about 3000
+fn generate_memory(rng: &mut Rng, characters: usize) -> String {
+	const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,;:'+-_ ?/~whatfromselectwhyhowwhere";
+	const FRAGMENTS: &[&str] = &["mul(", "mul[", "mul (", "do(", "don't", ")", ","];
+
+	let mut content = String::new();
+	let mut line_length = 0;
+
+	while content.len() < characters {
+		let token = match rng.below(10) {
+			0 | 1 => format!("mul({},{})", rng.range(1..=999), rng.range(1..=999)),
+			2 => rng.choose(&["do()", "don't()"]).to_string(),
+			3 => rng.choose(FRAGMENTS).to_string(),
+			_ => char::from(*rng.choose(JUNK)).to_string(),
+		};
+		line_length += token.len();
+		content.push_str(&token);
+
+		if line_length >= 3000 {
+			content.push('\n');
+			line_length = 0;
+		}
+	}
+
+	if !content.ends_with('\n') {
+		content.push('\n');
+	}
+	content
+}
@@ -0,0 +1,55 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+/// The number of 1 to 3 digits at the start of `text`, and its length
+fn number(text: &[u8]) -> Option<(usize, usize)> {
+	let digits = text.iter().take_while(|byte| byte.is_ascii_digit()).count();
+	if !(1..=3).contains(&digits) {
+		return None;
+	}
+	let value = text[..digits]
+		.iter()
+		.fold(0, |value, digit| value * 10 + (digit - b'0') as usize);
+	Some((value, digits))
+}
+
+/// The product of a `mul(X,Y)` instruction at the start of `text`
+fn mul(text: &[u8]) -> Option<usize> {
+	let text = text.strip_prefix(b"mul(")?;
+	let (x, length) = number(text)?;
+	let text = text[length..].strip_prefix(b",")?;
+	let (y, length) = number(text)?;
+	text[length..].strip_prefix(b")")?;
+	Some(x * y)
+}
+
+/// Try every position of the memory, optionally following `do()` and `don't()`
+fn sum_muls(input: &str, conditional: bool) -> usize {
+	let bytes = input.as_bytes();
+	let mut enabled = true;
+	let mut sum = 0;
+
+	for start in 0..bytes.len() {
+		let text = &bytes[start..];
+		if text.starts_with(b"do()") {
+			enabled = true;
+		} else if text.starts_with(b"don't()") {
+			enabled = !conditional;
+		} else if let Some(product) = mul(text) {
+			if enabled {
+				sum += product;
+			}
+		}
+	}
+
+	sum
+}
+
+/// Add up every `mul` instruction
+pub fn part_one(input: &str) -> usize {
+	sum_muls(input, false)
+}
+
+/// Add up the `mul` instructions enabled by `do()` and `don't()`
+pub fn part_two(input: &str) -> usize {
+	sum_muls(input, true)
+}
@@ -1,143 +1,163 @@
-use std::{
-	collections::{HashMap, HashSet},
-	fs,
+mod reference;
+
+use crate::{
+	cancel,
+	error::ParseError,
+	geometry::Point,
+	grid::{Grid, Position},
+	params::{Param, Parameters, Values},
+	rng::Rng,
+	solution::{Example, InputSize, Solution},
+	trace,
 };
 
-pub fn main() {
-	let (rules, updates) = read_input();
-	let dependency_map = rules_to_dependency_map(&rules);
-
-	let sum_ordered = sum_middle_ordered_updates(&updates, &dependency_map);
-	let sum_unordered = sum_middle_unordered_updates(&updates, &dependency_map);
-
-	println!("Sum of middle ordered updates: {}", sum_ordered);
-	println!("Sum of middle unordered updates: {}", sum_unordered);
+/// The word searched for in the first part
+pub struct SearchWord {
+	letters: Vec<char>,
 }
 
-/// Read the input file into page ordering rules and updates
-fn read_input() -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
-	let content = fs::read_to_string("./src/days/day05/input").expect("Unable to read file.");
-
-	// the empty line separates the rules from the updates
-	let parts: Vec<&str> = content.split("\n\n").collect();
-
-	// rules format: number|number
-	let rules = parts[0]
-		.lines()
-		.map(|line| {
-			let mut parts = line.split("|");
-			let start = parts.next().unwrap().parse().unwrap();
-			let end = parts.next().unwrap().parse().unwrap();
-			(start, end)
+impl Parameters for SearchWord {
+	const DECLARED: &'static [Param] = &[Param {
+		name: "word",
+		default: "XMAS",
+		description: "The word counted in every direction in the first part",
+	}];
+
+	fn from_values(values: &Values) -> Result<Self, String> {
+		let word: String = values.get("word")?;
+		if word.is_empty() {
+			return Err("parameter `word` must not be empty".to_string());
+		}
+		Ok(SearchWord {
+			letters: word.chars().collect(),
 		})
-		.collect();
+	}
+}
 
-	// updates format: list of comma separated numbers
-	let updates = parts[1]
-		.lines()
-		.map(|line| line.split(",").map(|num| num.parse().unwrap()).collect())
-		.collect();
+pub struct Day04;
 
-	(rules, updates)
-}
+impl Solution for Day04 {
+	type Input = Grid<char>;
+	type PartOne = usize;
+	type PartTwo = usize;
+	type Params = SearchWord;
 
-/// Convert the rules into dependency map
-fn rules_to_dependency_map(rules: &[(usize, usize)]) -> HashMap<usize, HashSet<usize>> {
-	let mut dependency_map = HashMap::new();
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("18"),
+		part_two: Some("9"),
+	}];
 
-	for (before, after) in rules {
-		dependency_map
-			.entry(*before)
-			.or_insert(HashSet::new())
-			.insert(*after);
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "grid width",
+		default: 140,
+		small: 10,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		Grid::parse(input, Ok)
 	}
 
-	dependency_map
-}
+	fn part_one(grid: &Self::Input, word: &SearchWord) -> usize {
+		count_word_occurrences(grid, &word.letters)
+	}
 
-/// Check if the update is ordered according to the dependency map
-fn is_update_ordered(update: &Vec<usize>, dependency_map: &HashMap<usize, HashSet<usize>>) -> bool {
-	for i in 0..update.len() - 1 {
-		let before = update.get(i).unwrap();
-		for j in i + 1..update.len() {
-			let after = update.get(j).unwrap();
-			// after -> before should not be in the dependency map
-			if dependency_map.contains_key(after)
-				&& dependency_map.get(after).unwrap().contains(before)
-			{
-				return false;
-			}
-		}
+	fn part_two(grid: &Self::Input, _word: &SearchWord) -> usize {
+		count_x_mas_occurrences(grid)
 	}
-	true
-}
 
-/// Sum the middle number of all ordered updates
-fn sum_middle_ordered_updates(
-	updates: &Vec<Vec<usize>>,
-	dependency_map: &HashMap<usize, HashSet<usize>>,
-) -> usize {
-	let mut sum = 0;
-	for update in updates {
-		if is_update_ordered(&update, dependency_map) {
-			let middle_number = update.get(update.len() / 2).unwrap();
-			sum += middle_number;
-		}
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_letters(rng, size)
 	}
-	sum
-}
 
-/// Topological sort an update
-fn topological_sort(
-	update: &Vec<usize>,
-	dependency_map: &HashMap<usize, HashSet<usize>>,
-) -> Vec<usize> {
-	let mut sorted = Vec::new();
-	let mut to_visit = update.clone();
-
-	while !to_visit.is_empty() {
-		let next = to_visit[to_visit.len() - 1];
-		visit(next, dependency_map, &mut to_visit, &mut sorted);
+	fn reference_one(input: &str, word: &SearchWord) -> usize {
+		reference::part_one(input, word)
 	}
 
-	sorted
+	fn reference_two(input: &str, _word: &SearchWord) -> usize {
+		reference::part_two(input)
+	}
 }
 
-/// Visit a node in the topological sort
-fn visit(
-	next: usize,
-	outgoing_edges: &HashMap<usize, HashSet<usize>>,
-	to_visit: &mut Vec<usize>,
-	sorted: &mut Vec<usize>,
-) {
-	if !to_visit.contains(&next) {
-		return;
+/// Number of occurrences of "XMAS" in every direction
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+	Ok(Day04::part_one(
+		&Day04::parse_input(input)?,
+		&SearchWord::defaults(),
+	))
+}
+
+/// Number of "MAS" crosses
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+	Ok(Day04::part_two(
+		&Day04::parse_input(input)?,
+		&SearchWord::defaults(),
+	))
+}
+
+/// Count the number of occurrences of a word in the grid
+fn count_word_occurrences(grid: &Grid<char>, target: &[char]) -> usize {
+	let mut count = 0;
+
+	// Check all 8 possible directions
+	for position in grid.positions() {
+		cancel::checkpoint();
+		for direction in Point::NEIGHBOURS {
+			if matches_target(grid, position, direction, target) {
+				trace!(Info, "word", start = position, direction = direction);
+				count += 1;
+			}
+		}
 	}
 
-	let index = to_visit.iter().position(|x| *x == next).unwrap();
-	to_visit.remove(index);
+	count
+}
+
+/// Check if the target word matches the grid starting at the given position and moving in the given direction
+fn matches_target(grid: &Grid<char>, start: Position, direction: Point, target: &[char]) -> bool {
+	let mut cells = grid.line(start, direction);
+	target.iter().all(|expected| cells.next() == Some(expected))
+}
 
-	if let Some(dependencies) = outgoing_edges.get(&next) {
-		for dependency in dependencies {
-			visit(*dependency, outgoing_edges, to_visit, sorted);
+/// Count the number of occurrences of the pattern X-"MAS" in the grid
+fn count_x_mas_occurrences(grid: &Grid<char>) -> usize {
+	let mut count = 0;
+
+	for (position, cell) in grid.iter() {
+		cancel::checkpoint();
+		if *cell == 'A' && is_valid_x_mas(grid, position) {
+			trace!(Info, "x-mas", center = position);
+			count += 1;
 		}
 	}
 
-	sorted.push(next);
+	count
 }
 
-/// Sum the middle number of all unordered updates
-fn sum_middle_unordered_updates(
-	updates: &Vec<Vec<usize>>,
-	dependency_map: &HashMap<usize, HashSet<usize>>,
-) -> usize {
-	let mut sum = 0;
-	for update in updates {
-		if !is_update_ordered(&update, dependency_map) {
-			let sorted = topological_sort(&update, dependency_map);
-			let middle_number = sorted.get(sorted.len() / 2).unwrap();
-			sum += middle_number;
+/// Check if the pattern X-"MAS" is valid at the given position
+fn is_valid_x_mas(grid: &Grid<char>, position: Position) -> bool {
+	let corner = |x, y| {
+		grid.offset(position, Point::new(x, y))
+			.map(|corner| grid[corner])
+	};
+
+	// each diagonal has to have one m and one s
+	let is_mas = |a, b| matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')));
+
+	// top left to bottom right, then top right to bottom left
+	is_mas(corner(-1, -1), corner(1, 1)) && is_mas(corner(1, -1), corner(-1, 1))
+}
+
+/// Generate a square grid of random `X`, `M`, `A` and `S` letters
+fn generate_letters(rng: &mut Rng, width: usize) -> String {
+	let mut content = String::with_capacity((width + 1) * width);
+
+	for _ in 0..width {
+		for _ in 0..width {
+			content.push(*rng.choose(&['X', 'M', 'A', 'S']));
 		}
+		content.push('\n');
 	}
-	sum
+
+	content
 }
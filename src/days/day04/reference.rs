@@ -0,0 +1,70 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use super::SearchWord;
+
+/// The letters of the grid, row by row
+fn letters(input: &str) -> Vec<Vec<char>> {
+	input
+		.lines()
+		.filter(|line| !line.is_empty())
+		.map(|line| line.chars().collect())
+		.collect()
+}
+
+/// The letter at a signed position, if it is within the grid
+fn letter(grid: &[Vec<char>], x: isize, y: isize) -> Option<char> {
+	let row = grid.get(usize::try_from(y).ok()?)?;
+	row.get(usize::try_from(x).ok()?).copied()
+}
+
+/// Read the word starting at every position in every direction
+pub fn part_one(input: &str, word: &SearchWord) -> usize {
+	let grid = letters(input);
+	let mut count = 0;
+
+	for y in 0..grid.len() as isize {
+		for x in 0..grid[0].len() as isize {
+			for (dx, dy) in [
+				(-1, -1),
+				(0, -1),
+				(1, -1),
+				(-1, 0),
+				(1, 0),
+				(-1, 1),
+				(0, 1),
+				(1, 1),
+			] {
+				let read = (0..word.letters.len() as isize)
+					.map(|step| letter(&grid, x + step * dx, y + step * dy))
+					.collect::<Option<Vec<char>>>();
+				if read.as_deref() == Some(&word.letters[..]) {
+					count += 1;
+				}
+			}
+		}
+	}
+
+	count
+}
+
+/// Compare the 3x3 square around every position with all four ways to write the cross
+pub fn part_two(input: &str) -> usize {
+	let grid = letters(input);
+	let crosses = ["MSAMS", "SMASM", "MMASS", "SSAMM"];
+	let mut count = 0;
+
+	for y in 0..grid.len() as isize {
+		for x in 0..grid[0].len() as isize {
+			// top left, top right, center, bottom left, bottom right
+			let read = [(-1, -1), (1, -1), (0, 0), (-1, 1), (1, 1)]
+				.iter()
+				.map(|(dx, dy)| letter(&grid, x + dx, y + dy))
+				.collect::<Option<String>>();
+			if read.is_some_and(|read| crosses.contains(&read.as_str())) {
+				count += 1;
+			}
+		}
+	}
+
+	count
+}
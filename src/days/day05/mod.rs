@@ -1,129 +1,262 @@
-use std::fs;
+mod reference;
 
-pub fn main() {
-	let content = read_input();
-	let grid = create_grid(&content);
-	let xmas_occurrences = count_xmas_occurrences(&grid);
-	let x_mas_occurrences = count_x_mas_occurrences(&grid);
+use std::collections::{HashMap, HashSet};
 
-	println!("XMAS occurrences: {}", xmas_occurrences);
-	println!("X-MAS occurrences: {}", x_mas_occurrences);
+use crate::{
+	cancel,
+	error::ParseError,
+	rng::Rng,
+	scan::Scanner,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
+
+/// A page ordering rule (before, after)
+type Rule = (usize, usize);
+
+/// The page dependencies and the updates to check against them
+pub struct PrintQueue {
+	dependency_map: HashMap<usize, HashSet<usize>>,
+	updates: Vec<Vec<usize>>,
 }
 
-/// Read the input file to bytes
-fn read_input() -> String {
-	fs::read_to_string("./src/days/day04/input").expect("Unable to read file.")
+pub struct Day05;
+
+impl Solution for Day05 {
+	type Input = PrintQueue;
+	type PartOne = u128;
+	type PartTwo = u128;
+	type Params = ();
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("143"),
+		part_two: Some("123"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "updates",
+		default: 200,
+		small: 10,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		let (rules, updates) = read_rules_and_updates(input)?;
+		let dependency_map = rules_to_dependency_map(&rules);
+
+		Ok(PrintQueue {
+			dependency_map,
+			updates,
+		})
+	}
+
+	fn part_one(queue: &Self::Input, _params: &()) -> u128 {
+		sum_middle_ordered_updates(&queue.updates, &queue.dependency_map)
+	}
+
+	fn part_two(queue: &Self::Input, _params: &()) -> u128 {
+		sum_middle_unordered_updates(&queue.updates, &queue.dependency_map)
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_print_queue(rng, size)
+	}
+
+	fn reference_one(input: &str, _params: &()) -> u128 {
+		reference::part_one(input)
+	}
+
+	fn reference_two(input: &str, _params: &()) -> u128 {
+		reference::part_two(input)
+	}
+}
+
+/// Sum of the middle pages of the correctly ordered updates
+pub fn part_one(input: &str) -> Result<u128, ParseError> {
+	Ok(Day05::part_one(&Day05::parse_input(input)?, &()))
+}
+
+/// Sum of the middle pages of the incorrectly ordered updates after ordering them
+pub fn part_two(input: &str) -> Result<u128, ParseError> {
+	Ok(Day05::part_two(&Day05::parse_input(input)?, &()))
 }
 
-/// Create a grid from the input
-fn create_grid(content: &String) -> Vec<Vec<char>> {
-	let mut grid = Vec::new();
-	for line in content.lines() {
-		let mut row = Vec::new();
-		for c in line.chars() {
-			row.push(c);
+/// Parse the input into page ordering rules and updates
+fn read_rules_and_updates(content: &str) -> Result<(Vec<Rule>, Vec<Vec<usize>>), ParseError> {
+	let mut scanner = Scanner::new(content.as_bytes());
+
+	// rules format: number|number, until the empty line separating the rules from the updates
+	let mut rules = Vec::new();
+	while !scanner.eat(b"\n") {
+		if scanner.is_empty() {
+			return Err(scanner.error("an empty line between the rules and the updates"));
 		}
-		grid.push(row);
+		let before = scanner.integer()?;
+		scanner.expect(b"|", "`|`")?;
+		let after = scanner.integer()?;
+		scanner.expect_line_end()?;
+		rules.push((before, after));
 	}
-	grid
-}
 
-/// Count the number of occurrences of the word "XMAS" in the grid
-fn count_xmas_occurrences(grid: &[Vec<char>]) -> usize {
-	let rows = grid.len();
-	let cols = grid[0].len();
-	let target = "XMAS".chars().collect::<Vec<char>>();
-	let target_len = target.len();
-
-	let mut count = 0;
-
-	for row in 0..rows {
-		for col in 0..cols {
-			// Check all 8 possible directions
-			let directions = [
-				(0, 1),   // right
-				(0, -1),  // left
-				(1, 0),   // down
-				(-1, 0),  // up
-				(1, 1),   // diagonal down-right
-				(-1, -1), // diagonal up-left
-				(1, -1),  // diagonal down-left
-				(-1, 1),  // diagonal up-right
-			];
-
-			for &(dr, dc) in &directions {
-				if matches_target(grid, row, col, dr, dc, &target, target_len) {
-					count += 1;
-				}
-			}
+	// updates format: list of comma separated numbers
+	let mut updates = Vec::new();
+	while !scanner.is_empty() {
+		let mut update = vec![scanner.integer()?];
+		while scanner.eat(b",") {
+			update.push(scanner.integer()?);
 		}
+		scanner.expect_line_end()?;
+		updates.push(update);
 	}
 
-	count
+	Ok((rules, updates))
 }
 
-/// Check if the target word matches the grid starting at the given position and moving in the given direction
-fn matches_target(
-	grid: &[Vec<char>],
-	start_row: usize,
-	start_col: usize,
-	increment_row: isize,
-	increment_column: isize,
-	target: &[char],
-	target_len: usize,
-) -> bool {
-	let rows = grid.len();
-	let cols = grid[0].len();
-
-	for i in 0..target_len {
-		let r = start_row as isize + i as isize * increment_row;
-		let c = start_col as isize + i as isize * increment_column;
-
-		if r < 0 || r >= rows as isize || c < 0 || c >= cols as isize {
-			return false;
+/// Convert the rules into dependency map
+fn rules_to_dependency_map(rules: &[Rule]) -> HashMap<usize, HashSet<usize>> {
+	let mut dependency_map = HashMap::new();
+
+	for (before, after) in rules {
+		dependency_map
+			.entry(*before)
+			.or_insert(HashSet::new())
+			.insert(*after);
+	}
+
+	dependency_map
+}
+
+/// Check if the update is ordered according to the dependency map
+fn is_update_ordered(update: &[usize], dependency_map: &HashMap<usize, HashSet<usize>>) -> bool {
+	for i in 0..update.len() - 1 {
+		let before = update.get(i).unwrap();
+		for j in i + 1..update.len() {
+			cancel::checkpoint();
+			let after = update.get(j).unwrap();
+			// after -> before should not be in the dependency map
+			if dependency_map.contains_key(after)
+				&& dependency_map.get(after).unwrap().contains(before)
+			{
+				return false;
+			}
 		}
+	}
+	true
+}
 
-		if grid[r as usize][c as usize] != target[i] {
-			return false;
+/// Sum the middle number of all ordered updates, as a `u128` so large pages cannot overflow
+fn sum_middle_ordered_updates(
+	updates: &[Vec<usize>],
+	dependency_map: &HashMap<usize, HashSet<usize>>,
+) -> u128 {
+	let mut sum = 0;
+	for update in updates {
+		if is_update_ordered(update, dependency_map) {
+			let middle_number = update.get(update.len() / 2).unwrap();
+			trace!(Info, "ordered", update = update, middle = middle_number);
+			sum += *middle_number as u128;
 		}
 	}
+	sum
+}
 
-	true
+/// Topological sort an update
+fn topological_sort(
+	update: &[usize],
+	dependency_map: &HashMap<usize, HashSet<usize>>,
+) -> Vec<usize> {
+	let mut sorted = Vec::new();
+	let mut to_visit = update.to_vec();
+
+	while !to_visit.is_empty() {
+		let next = to_visit[to_visit.len() - 1];
+		visit(next, dependency_map, &mut to_visit, &mut sorted);
+	}
+
+	sorted
 }
 
-/// Count the number of occurrences of the pattern X-"MAS" in the grid
-fn count_x_mas_occurrences(grid: &[Vec<char>]) -> usize {
-	let rows = grid.len();
-	let cols = grid[0].len();
+/// Visit a node in the topological sort
+fn visit(
+	next: usize,
+	outgoing_edges: &HashMap<usize, HashSet<usize>>,
+	to_visit: &mut Vec<usize>,
+	sorted: &mut Vec<usize>,
+) {
+	cancel::checkpoint();
+	if !to_visit.contains(&next) {
+		return;
+	}
 
-	let mut count = 0;
+	let index = to_visit.iter().position(|x| *x == next).unwrap();
+	to_visit.remove(index);
 
-	// Only iterate over inner characters
-	for row in 1..rows - 1 {
-		for col in 1..cols - 1 {
-			if grid[row][col] == 'A' && is_valid_x_mas(grid, row, col) {
-				count += 1;
-			}
+	if let Some(dependencies) = outgoing_edges.get(&next) {
+		for dependency in dependencies {
+			visit(*dependency, outgoing_edges, to_visit, sorted);
 		}
 	}
 
-	count
+	sorted.push(next);
+}
+
+/// Sum the middle number of all unordered updates, in `u128` like the ordered ones
+fn sum_middle_unordered_updates(
+	updates: &[Vec<usize>],
+	dependency_map: &HashMap<usize, HashSet<usize>>,
+) -> u128 {
+	let mut sum = 0;
+	for update in updates {
+		if !is_update_ordered(update, dependency_map) {
+			let sorted = topological_sort(update, dependency_map);
+			let middle_number = sorted.get(sorted.len() / 2).unwrap();
+			trace!(
+				Info,
+				"reordered",
+				update = update,
+				sorted = sorted,
+				middle = middle_number
+			);
+			sum += *middle_number as u128;
+		}
+	}
+	sum
 }
 
-/// Check if the pattern X-"MAS" is valid at the given position
-fn is_valid_x_mas(grid: &[Vec<char>], row: usize, col: usize) -> bool {
-	// check that top left to bottom right diagonal has one m and one s
-	if !((grid[row - 1][col - 1] == 'M' && grid[row + 1][col + 1] == 'S')
-		|| (grid[row - 1][col - 1] == 'S' && grid[row + 1][col + 1] == 'M'))
-	{
-		return false;
+/// Generate the rules of a random order of 49 pages, and `updates` updates of an odd number of pages
+///
+/// As in the real inputs every pair of pages has a rule, and about half of the updates are ordered.
+fn generate_print_queue(rng: &mut Rng, updates: usize) -> String {
+	let mut pages = (10..=99).collect::<Vec<usize>>();
+	rng.shuffle(&mut pages);
+	pages.truncate(49);
+
+	let mut rules = Vec::new();
+	for (index, before) in pages.iter().enumerate() {
+		for after in &pages[index + 1..] {
+			rules.push(format!("{}|{}\n", before, after));
+		}
 	}
+	rng.shuffle(&mut rules);
+
+	let mut content = rules.concat();
+	content.push('\n');
+
+	for _ in 0..updates {
+		let mut indices = (0..pages.len()).collect::<Vec<usize>>();
+		rng.shuffle(&mut indices);
+		indices.truncate(2 * rng.range(2..=11) + 1);
+		if rng.chance(1, 2) {
+			indices.sort();
+		}
 
-	if !((grid[row - 1][col + 1] == 'M' && grid[row + 1][col - 1] == 'S')
-		|| (grid[row - 1][col + 1] == 'S' && grid[row + 1][col - 1] == 'M'))
-	{
-		return false;
+		let update = indices
+			.iter()
+			.map(|index| pages[*index].to_string())
+			.collect::<Vec<_>>();
+		content.push_str(&update.join(","));
+		content.push('\n');
 	}
 
-	return true;
+	content
 }
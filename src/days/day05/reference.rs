@@ -0,0 +1,62 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use std::collections::HashSet;
+
+/// The rules and the updates of the input
+fn rules_and_updates(input: &str) -> (HashSet<(usize, usize)>, Vec<Vec<usize>>) {
+	let (rules, updates) = input.split_once("\n\n").unwrap();
+	let rules = rules
+		.lines()
+		.map(|line| {
+			let (before, after) = line.split_once('|').unwrap();
+			(before.parse().unwrap(), after.parse().unwrap())
+		})
+		.collect();
+	let updates = updates
+		.lines()
+		.filter(|line| !line.is_empty())
+		.map(|line| line.split(',').map(|page| page.parse().unwrap()).collect())
+		.collect();
+	(rules, updates)
+}
+
+/// The first pair of positions of the update that breaks a rule
+fn broken_rule(update: &[usize], rules: &HashSet<(usize, usize)>) -> Option<(usize, usize)> {
+	for i in 0..update.len() {
+		for j in i + 1..update.len() {
+			if rules.contains(&(update[j], update[i])) {
+				return Some((i, j));
+			}
+		}
+	}
+	None
+}
+
+/// Check every pair of pages of every update against all rules
+pub fn part_one(input: &str) -> u128 {
+	let (rules, updates) = rules_and_updates(input);
+	updates
+		.iter()
+		.filter(|update| broken_rule(update, &rules).is_none())
+		.map(|update| update[update.len() / 2] as u128)
+		.sum()
+}
+
+/// Swap the pages of broken rules until the unordered updates are ordered
+pub fn part_two(input: &str) -> u128 {
+	let (rules, updates) = rules_and_updates(input);
+	let mut sum = 0;
+
+	for update in updates {
+		if broken_rule(&update, &rules).is_none() {
+			continue;
+		}
+		let mut update = update;
+		while let Some((i, j)) = broken_rule(&update, &rules) {
+			update.swap(i, j);
+		}
+		sum += update[update.len() / 2] as u128;
+	}
+
+	sum
+}
@@ -1,180 +1,183 @@
-use std::{
-	collections::{HashMap, HashSet},
-	fs,
+mod reference;
+
+use std::collections::HashSet;
+
+use crate::{
+	cancel,
+	error::ParseError,
+	geometry::Direction,
+	grid::{Grid, Position},
+	rng::Rng,
+	solution::{Example, InputSize, Solution},
+	trace,
 };
 
-/// Direction enum with (x_increment, y_increment)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-	Up,
-	Down,
-	Left,
-	Right,
+/// The guard's starting state and the map it patrols
+pub struct Map {
+	start_position: Position,
+	start_direction: Direction,
+	/// Whether each position is obstructed
+	obstacles: Grid<bool>,
 }
 
-impl Direction {
-	fn get_increment(&self) -> (isize, isize) {
-		match *self {
-			Direction::Up => (0, -1),
-			Direction::Down => (0, 1),
-			Direction::Left => (-1, 0),
-			Direction::Right => (1, 0),
-		}
+pub struct Day06;
+
+impl Solution for Day06 {
+	type Input = Map;
+	type PartOne = usize;
+	type PartTwo = usize;
+	type Params = ();
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("41"),
+		part_two: Some("6"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "grid width",
+		default: 130,
+		small: 10,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		read_map(input)
 	}
 
-	fn turn(&self) -> Self {
-		match *self {
-			Direction::Up => Direction::Right,
-			Direction::Right => Direction::Down,
-			Direction::Down => Direction::Left,
-			Direction::Left => Direction::Up,
-		}
+	fn part_one(map: &Self::Input, _params: &()) -> usize {
+		count_unique_positions(map.start_position, map.start_direction, &map.obstacles)
 	}
-}
 
-pub fn main() {
-	let (current_position, current_direction, obstacles, grid_size) = read_input();
+	fn part_two(map: &Self::Input, _params: &()) -> usize {
+		count_loop_positions(map.start_position, map.start_direction, &map.obstacles)
+	}
 
-	let unique_positions =
-		count_unique_positions(current_position, &current_direction, &obstacles, grid_size);
-	println!("Unique positions visited: {}", unique_positions);
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_map(rng, size)
+	}
 
-	let loop_positions =
-		count_loop_positions(current_position, &current_direction, &obstacles, grid_size);
-	println!(
-		"Positions for one additional obstruction to cause a loop: {}",
-		loop_positions
-	);
-}
+	fn reference_one(input: &str, _params: &()) -> usize {
+		reference::part_one(input)
+	}
 
-/// Read the input file into a tuple of (current_position, current_direction, obstacles, grid_size)
-fn read_input() -> (
-	(usize, usize),
-	Direction,
-	HashSet<(usize, usize)>,
-	(usize, usize),
-) {
-	let content = fs::read_to_string("./src/days/day06/input").expect("Unable to read file.");
-
-	let mut current_position = (0, 0);
-	let current_direction = Direction::Up;
-	let mut obstacles = HashSet::new();
-	let mut grid_width = 0;
-
-	let mut y_pos = 0;
-	for line in content.lines() {
-		let mut x_pos = 0;
-		for c in line.chars() {
-			if c == '#' {
-				obstacles.insert((x_pos, y_pos));
-			} else if c == '^' {
-				current_position = (x_pos, y_pos);
-			}
-			x_pos += 1;
-		}
-		grid_width = x_pos;
-		y_pos += 1;
+	fn reference_two(input: &str, _params: &()) -> usize {
+		reference::part_two(input)
 	}
+}
+
+/// Number of distinct positions the guard visits before leaving the map
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+	Ok(Day06::part_one(&Day06::parse_input(input)?, &()))
+}
 
-	(
-		current_position,
-		current_direction,
-		obstacles,
-		(grid_width, y_pos),
-	)
+/// Number of positions where a single new obstruction traps the guard in a loop
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+	Ok(Day06::part_two(&Day06::parse_input(input)?, &()))
+}
+
+/// Parse the input into the guard's position and direction and the obstacles
+fn read_map(content: &str) -> Result<Map, ParseError> {
+	let mut guards = 0;
+	let grid = Grid::parse(content, |c| match c {
+		'.' | '#' => Ok(c),
+		'^' if guards == 0 => {
+			guards += 1;
+			Ok(c)
+		}
+		_ if guards == 0 => Err("`.`, `#` or `^`"),
+		_ => Err("`.` or `#`"),
+	})?;
+
+	let current_position = grid
+		.position(|c| *c == '^')
+		.ok_or_else(|| ParseError::at(content.as_bytes(), content.len(), "a guard `^`"))?;
+
+	Ok(Map {
+		start_position: current_position,
+		start_direction: Direction::Up,
+		obstacles: grid.map(|c| *c == '#'),
+	})
 }
 
 /// Count the number of unique positions the guard will visit before leaving the map
+///
+/// If the map traps the guard in a loop, the positions on the way into and along the loop count.
 fn count_unique_positions(
-	current_position: (usize, usize),
-	current_direction: &Direction,
-	obstacles: &HashSet<(usize, usize)>,
-	grid_size: (usize, usize),
+	current_position: Position,
+	current_direction: Direction,
+	obstacles: &Grid<bool>,
 ) -> usize {
-	let mut visited_positions = HashSet::new();
+	let mut visited_states = HashSet::new();
 	let mut current_position = current_position;
-	let mut current_direction = current_direction.clone();
+	let mut current_direction = current_direction;
 
-	loop {
-		visited_positions.insert(current_position);
-
-		let (x, y) = current_position;
-		let (dx, dy) = current_direction.get_increment();
-		let new_position = (x as isize + dx, y as isize + dy);
-
-		if new_position.0 < 0
-			|| new_position.0 >= grid_size.0 as isize
-			|| new_position.1 < 0
-			|| new_position.1 >= grid_size.1 as isize
-		{
+	while visited_states.insert((current_position, current_direction)) {
+		cancel::checkpoint();
+		let Some(new_position) = obstacles.offset(current_position, current_direction.step())
+		else {
 			break;
-		}
-
-		let new_position = (new_position.0 as usize, new_position.1 as usize);
-
-		if obstacles.contains(&new_position) {
-			current_direction = current_direction.turn();
+		};
+
+		if obstacles[new_position] {
+			current_direction = current_direction.turn_right();
+			trace!(
+				Debug,
+				"turn",
+				position = current_position,
+				direction = current_direction
+			);
 		} else {
 			current_position = new_position;
 		}
 	}
 
-	visited_positions.len()
+	visited_states
+		.into_iter()
+		.map(|(position, _)| position)
+		.collect::<HashSet<_>>()
+		.len()
 }
 
 /// Count the number of positions for one additional obstruction to cause a loop
 fn count_loop_positions(
-	current_position: (usize, usize),
-	current_direction: &Direction,
-	obstacles: &HashSet<(usize, usize)>,
-	grid_size: (usize, usize),
+	current_position: Position,
+	current_direction: Direction,
+	obstacles: &Grid<bool>,
 ) -> usize {
-	let mut visited_positions: HashSet<((usize, usize), Direction)> = HashSet::new();
+	let mut visited_positions: HashSet<(Position, Direction)> = HashSet::new();
 	let mut count = 0;
 	let initial_position = current_position;
-	let initial_direction = current_direction.clone();
+	let initial_direction = current_direction;
 	let mut current_position = current_position;
-	let mut current_direction = current_direction.clone();
+	let mut current_direction = current_direction;
 	let mut all_obstacles = obstacles.clone();
-	let mut positions_of_additional_obstacles_tries: HashSet<(usize, usize)> = HashSet::new();
+	let mut positions_of_additional_obstacles_tries: HashSet<Position> = HashSet::new();
 
-	loop {
-		visited_positions.insert((current_position, current_direction.clone()));
-
-		let (x, y) = current_position;
-		let (dx, dy) = current_direction.get_increment();
-		let new_position = (x as isize + dx, y as isize + dy);
-
-		if new_position.0 < 0
-			|| new_position.0 >= grid_size.0 as isize
-			|| new_position.1 < 0
-			|| new_position.1 >= grid_size.1 as isize
-		{
+	// stops at the exit, or when the guard walks in a loop without an additional obstacle
+	while visited_positions.insert((current_position, current_direction)) {
+		cancel::checkpoint();
+		let Some(new_position) = obstacles.offset(current_position, current_direction.step())
+		else {
 			break;
-		}
-
-		let new_position = (new_position.0 as usize, new_position.1 as usize);
+		};
 
 		if initial_position != new_position {
 			// check if the current position is already tried as an additional obstacle
 			if positions_of_additional_obstacles_tries.insert(new_position) {
 				// temporaily check if adding an obstacle at the current position would cause a loop
-				if all_obstacles.insert(new_position) {
-					if is_loop(
-						initial_position,
-						&initial_direction,
-						&all_obstacles,
-						grid_size,
-					) {
+				if !all_obstacles[new_position] {
+					all_obstacles[new_position] = true;
+					if is_loop(initial_position, initial_direction, &all_obstacles) {
+						trace!(Info, "loop obstacle", position = new_position);
 						count += 1;
 					}
-					assert!(all_obstacles.remove(&new_position));
+					all_obstacles[new_position] = false;
 				}
 			}
 		}
 
-		if obstacles.contains(&new_position) {
-			current_direction = current_direction.turn();
+		if obstacles[new_position] {
+			current_direction = current_direction.turn_right();
 		} else {
 			current_position = new_position;
 		}
@@ -185,36 +188,27 @@ fn count_loop_positions(
 
 /// Function to detect a loop
 fn is_loop(
-	current_position: (usize, usize),
-	current_direction: &Direction,
-	all_obstacles: &HashSet<(usize, usize)>,
-	grid_size: (usize, usize),
+	current_position: Position,
+	current_direction: Direction,
+	all_obstacles: &Grid<bool>,
 ) -> bool {
-	let mut visited_positions: HashSet<((usize, usize), Direction)> = HashSet::new();
+	let mut visited_positions: HashSet<(Position, Direction)> = HashSet::new();
 	let mut current_position = current_position;
-	let mut current_direction = current_direction.clone();
+	let mut current_direction = current_direction;
 
 	loop {
-		if !visited_positions.insert((current_position, current_direction.clone())) {
+		if !visited_positions.insert((current_position, current_direction)) {
 			return true;
 		}
+		cancel::checkpoint();
 
-		let (x, y) = current_position;
-		let (dx, dy) = current_direction.get_increment();
-		let new_position = (x as isize + dx, y as isize + dy);
-
-		if new_position.0 < 0
-			|| new_position.0 >= grid_size.0 as isize
-			|| new_position.1 < 0
-			|| new_position.1 >= grid_size.1 as isize
-		{
+		let Some(new_position) = all_obstacles.offset(current_position, current_direction.step())
+		else {
 			break;
-		}
-
-		let new_position = (new_position.0 as usize, new_position.1 as usize);
+		};
 
-		if all_obstacles.contains(&new_position) {
-			current_direction = current_direction.turn();
+		if all_obstacles[new_position] {
+			current_direction = current_direction.turn_right();
 		} else {
 			current_position = new_position;
 		}
@@ -222,3 +216,50 @@ fn is_loop(
 
 	false
 }
+
+/// Generate a square map with about one obstacle in 20 positions and a guard who leaves it
+fn generate_map(rng: &mut Rng, width: usize) -> String {
+	let cells = (0..width * width).map(|_| rng.chance(1, 20)).collect();
+	let mut obstacles = Grid::new(width, width, cells);
+	let start = (rng.below(width), rng.below(width));
+	obstacles[start] = false;
+
+	// the guard has to leave the map, so break every loop the guard walks into
+	while let Some(obstacle) = closing_obstacle(start, &obstacles) {
+		obstacles[obstacle] = false;
+	}
+
+	let mut content = String::with_capacity((width + 1) * width);
+	for y in 0..width {
+		for x in 0..width {
+			content.push(match obstacles[(x, y)] {
+				_ if (x, y) == start => '^',
+				true => '#',
+				false => '.',
+			});
+		}
+		content.push('\n');
+	}
+
+	content
+}
+
+/// The obstacle at which the guard first repeats a turn, if the guard walks in a loop
+fn closing_obstacle(start: Position, obstacles: &Grid<bool>) -> Option<Position> {
+	let mut turns = HashSet::new();
+	let mut position = start;
+	let mut direction = Direction::Up;
+
+	while let Some(next) = obstacles.offset(position, direction.step()) {
+		if obstacles[next] {
+			if !turns.insert((position, direction)) {
+				return Some(next);
+			}
+			direction = direction.turn_right();
+		} else {
+			position = next;
+		}
+	}
+
+	None
+}
@@ -0,0 +1,70 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use std::collections::HashSet;
+
+/// The map as rows of cells
+fn cells(input: &str) -> Vec<Vec<u8>> {
+	input
+		.lines()
+		.filter(|line| !line.is_empty())
+		.map(|line| line.bytes().collect())
+		.collect()
+}
+
+/// The positions the guard visits, or `None` if the guard walks in a loop
+fn patrol(map: &[Vec<u8>]) -> Option<HashSet<(isize, isize)>> {
+	let y = map.iter().position(|row| row.contains(&b'^')).unwrap();
+	let x = map[y].iter().position(|cell| *cell == b'^').unwrap();
+	let (mut x, mut y) = (x as isize, y as isize);
+	let (mut dx, mut dy) = (0, -1);
+	let mut states = HashSet::new();
+
+	loop {
+		if !states.insert((x, y, dx, dy)) {
+			return None;
+		}
+		let (next_x, next_y) = (x + dx, y + dy);
+		let Some(cell) = usize::try_from(next_y)
+			.ok()
+			.and_then(|next_y| map.get(next_y))
+			.and_then(|row| row.get(usize::try_from(next_x).ok()?))
+		else {
+			break;
+		};
+		if *cell == b'#' {
+			(dx, dy) = (-dy, dx);
+		} else {
+			(x, y) = (next_x, next_y);
+		}
+	}
+
+	Some(states.into_iter().map(|(x, y, _, _)| (x, y)).collect())
+}
+
+/// Walk the guard step by step
+pub fn part_one(input: &str) -> usize {
+	patrol(&cells(input))
+		.expect("the guard leaves the map")
+		.len()
+}
+
+/// Place an obstruction on every free position and walk the guard
+pub fn part_two(input: &str) -> usize {
+	let mut map = cells(input);
+	let mut count = 0;
+
+	for y in 0..map.len() {
+		for x in 0..map[y].len() {
+			if map[y][x] != b'.' {
+				continue;
+			}
+			map[y][x] = b'#';
+			if patrol(&map).is_none() {
+				count += 1;
+			}
+			map[y][x] = b'.';
+		}
+	}
+
+	count
+}
@@ -1,123 +1,288 @@
-use std::{collections::HashSet, fs};
+mod reference;
 
-#[derive(Debug, Eq, Hash, PartialEq)]
-enum Operator {
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+	cancel,
+	error::ParseError,
+	params::{Param, Parameters, Values},
+	rng::Rng,
+	scan::Scanner,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
+
+/// An operator that can be placed between the numbers of an equation
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Operator {
 	Add,
 	Multiply,
 	Concatenate,
 }
 
 impl Operator {
-	fn apply(&self, a: usize, b: usize) -> usize {
+	/// The result of `a <operator> b`, or `None` if it overflows
+	fn apply(&self, a: usize, b: usize) -> Option<usize> {
 		match self {
-			Operator::Add => a + b,
-			Operator::Multiply => a * b,
+			Operator::Add => a.checked_add(b),
+			Operator::Multiply => a.checked_mul(b),
 			Operator::Concatenate => {
-				// 12 || 345 => 12345
-				let mut a = a;
-				let mut b_copy = b;
+				// 12 || 345 => 12345, 12 || 0 => 120
+				let mut a = a.checked_mul(10)?;
+				let mut b_copy = b / 10;
 				while b_copy > 0 {
-					a *= 10;
+					a = a.checked_mul(10)?;
 					b_copy /= 10;
 				}
-				a + b
+				a.checked_add(b)
 			}
 		}
 	}
-}
 
-pub fn main() {
-	let equations = read_input();
-
-	let sum_ways_solvable = sum_ways_to_solve(&equations, {
-		let mut operators = HashSet::new();
-		operators.insert(Operator::Add);
-		operators.insert(Operator::Multiply);
-		operators
-	});
-	println!("Sum of ways to solve: {}", sum_ways_solvable);
-
-	let sum_ways_solvable_with_concatenation = sum_ways_to_solve(&equations, {
-		let mut operators = HashSet::new();
-		operators.insert(Operator::Add);
-		operators.insert(Operator::Multiply);
-		operators.insert(Operator::Concatenate);
-		operators
-	});
-	println!(
-		"Sum of ways to solve with concatenation: {}",
-		sum_ways_solvable_with_concatenation
-	);
+	/// The left operand `a` with `a <operator> b == result`, if there is exactly one
+	///
+	/// A multiplication by zero has no single left operand, it is handled by the solver.
+	fn undo(&self, result: usize, b: usize) -> Option<usize> {
+		match self {
+			Operator::Add => result.checked_sub(b),
+			Operator::Multiply => (b != 0 && result.is_multiple_of(b)).then(|| result / b),
+			Operator::Concatenate => {
+				// 12345 || 345 => 12
+				let mut shift = 10usize;
+				let mut b_copy = b / 10;
+				while b_copy > 0 {
+					// only 0 can be shifted this far without overflowing
+					let Some(next) = shift.checked_mul(10) else {
+						return (result == b).then_some(0);
+					};
+					shift = next;
+					b_copy /= 10;
+				}
+				let shifted = result.checked_sub(b)?;
+				shifted.is_multiple_of(shift).then(|| shifted / shift)
+			}
+		}
+	}
 }
 
-/// Read the input file into a Set of tuples (usize, Vec<usize>)
-fn read_input() -> HashSet<(usize, Vec<usize>)> {
-	// each line has the format
-	// <number>: <number> <number>...
-	let content = fs::read_to_string("./src/days/day07/input").expect("Unable to read file.");
+impl FromStr for Operator {
+	type Err = ();
 
-	let mut equations = HashSet::new();
-	for line in content.lines() {
-		let mut parts = line.split(": ");
-		let number = parts.next().unwrap().parse::<usize>().unwrap();
-		let mut numbers = Vec::new();
-		for number in parts.next().unwrap().split(" ") {
-			numbers.push(number.parse::<usize>().unwrap());
+	/// Parse `+`, `*` or `||`
+	fn from_str(s: &str) -> Result<Self, ()> {
+		match s {
+			"+" => Ok(Operator::Add),
+			"*" => Ok(Operator::Multiply),
+			"||" => Ok(Operator::Concatenate),
+			_ => Err(()),
 		}
-		equations.insert((number, numbers));
 	}
+}
 
-	equations
+/// The operators available in each part
+pub struct OperatorSets {
+	part_one: HashSet<Operator>,
+	part_two: HashSet<Operator>,
 }
 
-/// Count ways to solve an equation by placing in + or * operators (left to right evaluation)
-fn count_ways_to_solve(equation: &(usize, Vec<usize>), operators: &HashSet<Operator>) -> usize {
-	let (target, numbers) = equation;
+impl Parameters for OperatorSets {
+	const DECLARED: &'static [Param] = &[
+		Param {
+			name: "part_one_operators",
+			default: "+,*",
+			description: "Comma separated operators of the first part, out of `+`, `*` and `||`",
+		},
+		Param {
+			name: "part_two_operators",
+			default: "+,*,||",
+			description: "Comma separated operators of the second part, out of `+`, `*` and `||`",
+		},
+	];
 
-	// If there's only one number, check if it matches the target
-	if numbers.len() == 1 {
-		return if numbers[0] == *target { 1 } else { 0 };
+	fn from_values(values: &Values) -> Result<Self, String> {
+		Ok(OperatorSets {
+			part_one: values.list("part_one_operators")?.into_iter().collect(),
+			part_two: values.list("part_two_operators")?.into_iter().collect(),
+		})
 	}
-
-	count_ways_to_solve_from(equation, &operators, numbers[0], 1)
 }
 
-fn count_ways_to_solve_from(
-	equation: &(usize, Vec<usize>),
-	operators: &HashSet<Operator>,
-	running_result: usize,
-	index: usize,
-) -> usize {
-	let (target, numbers) = equation;
+pub struct Day07;
+
+impl Solution for Day07 {
+	type Input = HashSet<(usize, Vec<usize>)>;
+	type PartOne = u128;
+	type PartTwo = u128;
+	type Params = OperatorSets;
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("3749"),
+		part_two: Some("11387"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "equations",
+		default: 850,
+		small: 10,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		read_equations(input)
+	}
+
+	fn part_one(equations: &Self::Input, operators: &OperatorSets) -> u128 {
+		sum_solvable(equations, &operators.part_one)
+	}
+
+	fn part_two(equations: &Self::Input, operators: &OperatorSets) -> u128 {
+		sum_solvable(equations, &operators.part_two)
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_equations(rng, size)
+	}
+
+	fn reference_one(input: &str, operators: &OperatorSets) -> u128 {
+		reference::part_one(input, operators)
+	}
 
-	if numbers.len() == index {
-		return if running_result == *target { 1 } else { 0 };
+	fn reference_two(input: &str, operators: &OperatorSets) -> u128 {
+		reference::part_two(input, operators)
 	}
+}
 
-	let mut count = 0;
+/// Sum of the test values of the equations solvable with `+` and `*`
+pub fn part_one(input: &str) -> Result<u128, ParseError> {
+	Ok(Day07::part_one(
+		&Day07::parse_input(input)?,
+		&OperatorSets::defaults(),
+	))
+}
+
+/// Sum of the test values of the equations solvable with `+`, `*` and `||`
+pub fn part_two(input: &str) -> Result<u128, ParseError> {
+	Ok(Day07::part_two(
+		&Day07::parse_input(input)?,
+		&OperatorSets::defaults(),
+	))
+}
 
-	for operator in operators {
-		let new_running_result = operator.apply(running_result, numbers[index]);
-		if new_running_result <= *target {
-			count += count_ways_to_solve_from(equation, &operators, new_running_result, index + 1);
+/// Sum of the test values of the equations solvable with the given operators
+pub fn solve(input: &str, operators: &[Operator]) -> Result<u128, ParseError> {
+	let equations = Day07::parse_input(input)?;
+	Ok(sum_solvable(
+		&equations,
+		&operators.iter().copied().collect(),
+	))
+}
+
+/// Parse the input into a Set of tuples (usize, Vec<usize>)
+fn read_equations(content: &str) -> Result<HashSet<(usize, Vec<usize>)>, ParseError> {
+	// each line has the format
+	// <number>: <number> <number>...
+	let mut equations = HashSet::new();
+	let mut scanner = Scanner::new(content.as_bytes());
+	while !scanner.is_empty() {
+		let number = scanner.integer()?;
+		scanner.expect(b": ", "`: `")?;
+		let mut numbers = vec![scanner.integer()?];
+		while scanner.eat(b" ") {
+			numbers.push(scanner.integer()?);
 		}
+		scanner.expect_line_end()?;
+		equations.insert((number, numbers));
 	}
 
-	count
+	Ok(equations)
 }
 
-/// Add count ways for solvable equations
-fn sum_ways_to_solve(
-	equations: &HashSet<(usize, Vec<usize>)>,
-	operators: HashSet<Operator>,
-) -> usize {
+/// Whether an equation can be solved by placing operators between the numbers (left to right
+/// evaluation)
+fn is_solvable(equation: &(usize, Vec<usize>), operators: &HashSet<Operator>) -> bool {
+	let (target, numbers) = equation;
+	is_solvable_until(numbers, operators, *target)
+}
+
+/// Whether the numbers can evaluate to `result`, undoing the operators from the right
+///
+/// Undoing prunes far more than evaluating from the left: only divisors can be undone
+/// multiplications and only suffixes undone concatenations.
+fn is_solvable_until(numbers: &[usize], operators: &HashSet<Operator>, result: usize) -> bool {
+	cancel::checkpoint();
+	let Some((&last, rest)) = numbers.split_last() else {
+		return false;
+	};
+	if rest.is_empty() {
+		return last == result;
+	}
+	// a multiplication by zero discards the value of everything before it
+	if last == 0 && result == 0 && operators.contains(&Operator::Multiply) {
+		return true;
+	}
+
+	operators.iter().any(|operator| {
+		operator
+			.undo(result, last)
+			.is_some_and(|result| is_solvable_until(rest, operators, result))
+	})
+}
+
+/// Add the test values of the solvable equations
+///
+/// Every test value can be up to `usize::MAX`, so the sum is a `u128`.
+fn sum_solvable(equations: &HashSet<(usize, Vec<usize>)>, operators: &HashSet<Operator>) -> u128 {
 	let mut sum = 0;
 
 	for equation in equations {
-		if count_ways_to_solve(equation, &operators) > 0 {
-			sum += equation.0;
+		if is_solvable(equation, operators) {
+			trace!(Info, "solvable", target = equation.0, numbers = equation.1);
+			sum += equation.0 as u128;
+		} else {
+			trace!(
+				Debug,
+				"unsolvable",
+				target = equation.0,
+				numbers = equation.1
+			);
 		}
 	}
 
 	sum
 }
+
+/// Generate equations of non-zero numbers, about half of which are solvable by construction
+fn generate_equations(rng: &mut Rng, equations: usize) -> String {
+	// keeps the running results of the solver well within usize
+	const LIMIT: usize = 1_000_000_000_000_000;
+	const OPERATORS: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concatenate];
+
+	let mut content = String::new();
+
+	for _ in 0..equations {
+		let numbers = (0..rng.range(2..=12))
+			.map(|_| match rng.below(3) {
+				0 => rng.range(1..=9),
+				1 => rng.range(10..=99),
+				_ => rng.range(100..=999),
+			})
+			.collect::<Vec<usize>>();
+
+		let target = if rng.chance(1, 2) {
+			numbers[1..].iter().fold(numbers[0], |value, number| {
+				rng.choose(&OPERATORS)
+					.apply(value, *number)
+					.filter(|result| *result < LIMIT)
+					.unwrap_or(value + number)
+			})
+		} else {
+			let digits = rng.range(2..=14) as u32;
+			rng.range(1..=10usize.pow(digits))
+		};
+
+		let numbers = numbers.iter().map(usize::to_string).collect::<Vec<_>>();
+		content.push_str(&format!("{}: {}\n", target, numbers.join(" ")));
+	}
+
+	content
+}
@@ -0,0 +1,70 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use std::collections::HashSet;
+
+use super::{Operator, OperatorSets};
+
+/// The test value and the numbers of every equation
+fn equations(input: &str) -> Vec<(u128, Vec<u128>)> {
+	input
+		.lines()
+		.filter(|line| !line.is_empty())
+		.map(|line| {
+			let (target, numbers) = line.split_once(": ").unwrap();
+			let numbers = numbers
+				.split(' ')
+				.map(|number| number.parse().unwrap())
+				.collect();
+			(target.parse().unwrap(), numbers)
+		})
+		.collect()
+}
+
+/// Apply an operator without overflowing, as `u128` holds more than any test value
+fn apply(operator: Operator, a: u128, b: u128) -> Option<u128> {
+	match operator {
+		Operator::Add => a.checked_add(b),
+		Operator::Multiply => a.checked_mul(b),
+		Operator::Concatenate => {
+			let mut shift = 10;
+			while shift <= b {
+				shift *= 10;
+			}
+			a.checked_mul(shift)?.checked_add(b)
+		}
+	}
+}
+
+/// Evaluate every assignment of operators to every equation
+fn sum_solvable(input: &str, operators: &HashSet<Operator>) -> u128 {
+	let operators = operators.iter().copied().collect::<Vec<_>>();
+	let mut sum = 0;
+
+	for (target, numbers) in equations(input) {
+		let gaps = numbers.len() as u32 - 1;
+		let solvable = (0..operators.len().pow(gaps)).any(|mut assignment| {
+			let mut value = Some(numbers[0]);
+			for number in &numbers[1..] {
+				let operator = operators[assignment % operators.len()];
+				assignment /= operators.len();
+				value = value.and_then(|value| apply(operator, value, *number));
+			}
+			value == Some(target)
+		});
+		if solvable {
+			sum += target;
+		}
+	}
+
+	sum
+}
+
+/// Try all operators of the first part everywhere
+pub fn part_one(input: &str, operators: &OperatorSets) -> u128 {
+	sum_solvable(input, &operators.part_one)
+}
+
+/// Try all operators of the second part everywhere
+pub fn part_two(input: &str, operators: &OperatorSets) -> u128 {
+	sum_solvable(input, &operators.part_two)
+}
@@ -1,138 +1,244 @@
-use std::{
-	collections::{HashMap, HashSet},
-	fs,
+mod reference;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+	cancel,
+	error::ParseError,
+	geometry::Point,
+	grid::{Grid, Position},
+	params::{Param, Parameters, Values},
+	rng::Rng,
+	solution::{Example, InputSize, Solution},
+	trace,
 };
 
-pub fn main() {
-	let (width, height, antennae_locations) = read_input();
+/// Antennae locations by frequency
+type AntennaeLocations = HashMap<char, HashSet<Position>>;
 
-	let antinodes = determine_all_antinodes(width, height, &antennae_locations, false);
-	println!("A total of {} antinodes were found.", antinodes.len());
+/// Whether each part includes resonant harmonics
+pub struct Harmonics {
+	part_one: bool,
+	part_two: bool,
+}
 
-	let antinodes = determine_all_antinodes(width, height, &antennae_locations, true);
-	println!(
-		"A total of {} antinodes were found, including resonant harmonics.",
-		antinodes.len()
-	);
+impl Parameters for Harmonics {
+	const DECLARED: &'static [Param] = &[
+		Param {
+			name: "part_one_harmonics",
+			default: "false",
+			description: "Whether the first part includes resonant harmonics",
+		},
+		Param {
+			name: "part_two_harmonics",
+			default: "true",
+			description: "Whether the second part includes resonant harmonics",
+		},
+	];
+
+	fn from_values(values: &Values) -> Result<Self, String> {
+		Ok(Harmonics {
+			part_one: values.get("part_one_harmonics")?,
+			part_two: values.get("part_two_harmonics")?,
+		})
+	}
 }
 
-/// Read and parse the input file
-fn read_input() -> (usize, usize, HashMap<char, HashSet<(usize, usize)>>) {
-	let content = fs::read_to_string("./src/days/day08/input").expect("Unable to read file.");
+pub struct Day08;
 
-	let mut width = 0;
-	let mut height = 0;
-	let mut antennae_locations = HashMap::new(); // map locations by frequency
+impl Solution for Day08 {
+	type Input = (Grid<char>, AntennaeLocations);
+	type PartOne = usize;
+	type PartTwo = usize;
+	type Params = Harmonics;
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("14"),
+		part_two: Some("34"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "grid width",
+		default: 50,
+		small: 12,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		read_antennae(input)
+	}
+
+	fn part_one((grid, antennae_locations): &Self::Input, harmonics: &Harmonics) -> usize {
+		determine_all_antinodes(grid, antennae_locations, harmonics.part_one).len()
+	}
+
+	fn part_two((grid, antennae_locations): &Self::Input, harmonics: &Harmonics) -> usize {
+		determine_all_antinodes(grid, antennae_locations, harmonics.part_two).len()
+	}
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_antennae(rng, size)
+	}
 
-	for (y, line) in content.lines().enumerate() {
-		if line.is_empty() {
-			break;
+	fn reference_one(input: &str, harmonics: &Harmonics) -> usize {
+		reference::part_one(input, harmonics)
+	}
+
+	fn reference_two(input: &str, harmonics: &Harmonics) -> usize {
+		reference::part_two(input, harmonics)
+	}
+}
+
+/// Number of unique antinode locations
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+	Ok(Day08::part_one(
+		&Day08::parse_input(input)?,
+		&Harmonics::defaults(),
+	))
+}
+
+/// Number of unique antinode locations including resonant harmonics
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+	Ok(Day08::part_two(
+		&Day08::parse_input(input)?,
+		&Harmonics::defaults(),
+	))
+}
+
+/// Number of unique antinode locations, optionally including resonant harmonics
+pub fn count_antinodes(input: &str, include_resonant_harmonics: bool) -> Result<usize, ParseError> {
+	let (grid, antennae_locations) = Day08::parse_input(input)?;
+	Ok(determine_all_antinodes(&grid, &antennae_locations, include_resonant_harmonics).len())
+}
+
+/// Parse the input into the map and the antennae locations
+fn read_antennae(content: &str) -> Result<(Grid<char>, AntennaeLocations), ParseError> {
+	let grid = Grid::parse(content, |c| {
+		if c.is_ascii_alphanumeric() || c == '.' {
+			Ok(c)
+		} else {
+			Err("`.` or an antenna frequency")
 		}
+	})?;
 
-		height = y + 1;
-		width = line.len();
-		for (x, c) in line.chars().enumerate() {
-			if c == '.' {
-				continue;
-			}
-			let entry = antennae_locations.entry(c).or_insert(HashSet::new());
-			entry.insert((x, y));
+	let mut antennae_locations = HashMap::new(); // map locations by frequency
+	for (position, c) in grid.iter() {
+		if *c == '.' {
+			continue;
 		}
+		let entry = antennae_locations.entry(*c).or_insert(HashSet::new());
+		entry.insert(position);
 	}
 
-	(width, height, antennae_locations)
+	Ok((grid, antennae_locations))
 }
 
 /// Get the antinodes for all antennae of a given frequency
 fn determine_antinodes(
-	width: usize,
-	height: usize,
-	locatons: &HashSet<(usize, usize)>,
+	grid: &Grid<char>,
+	locatons: &HashSet<Position>,
 	include_resonant_harmonics: bool,
-) -> HashSet<(usize, usize)> {
+) -> HashSet<Position> {
 	let mut antinodes = HashSet::new();
 
-	// loop over all pairs
-	for (x1, y1) in locatons.iter() {
-		let (x1_isize, y1_isize) = (*x1 as isize, *y1 as isize);
+	// loop over all pairs, both orders of a pair cover the antinodes on either side
+	for &first in locatons.iter() {
+		for &second in locatons.iter() {
+			cancel::checkpoint();
+			if first == second {
+				continue;
+			}
+
+			let difference = Point::from(second) - Point::from(first);
 
-		for (x2, y2) in locatons.iter() {
-			if x1 == x2 && y1 == y2 {
+			if include_resonant_harmonics {
+				// the smallest step between grid positions on the line, e.g. (1, 3) for (2, 6)
+				let divisor =
+					gcd(difference.x.unsigned_abs(), difference.y.unsigned_abs()) as isize;
+				let step = Point::new(difference.x / divisor, difference.y / divisor);
+				antinodes.extend(determine_antinodes_starting_in(grid, first, step));
 				continue;
 			}
 
-			let (x2_isize, y2_isize) = (*x2 as isize, *y2 as isize);
-
-			let (dx, dy) = (x2_isize - x1_isize, y2_isize - y1_isize);
-
-			antinodes.extend(determine_antinodes_starting_in(
-				width,
-				height,
-				(*x1, *y1),
-				(-dx, -dy),
-				include_resonant_harmonics,
-			));
-
-			antinodes.extend(determine_antinodes_starting_in(
-				width,
-				height,
-				(*x2, *y2),
-				(dx, dy),
-				include_resonant_harmonics,
-			));
+			// beyond the second antenna, twice as far from the first one
+			antinodes.extend(grid.offset(second, difference));
+
+			// between the antennae, twice as far from the second one
+			if difference.x % 3 == 0 && difference.y % 3 == 0 {
+				let third = Point::new(difference.x / 3, difference.y / 3);
+				antinodes.extend(grid.offset(first, third));
+			}
 		}
 	}
 
 	antinodes
 }
 
-/// Determine all antinodes in a direction starting in a given location
+/// Determine all positions on the grid from a given location onwards in steps of `increment`
 fn determine_antinodes_starting_in(
-	width: usize,
-	height: usize,
-	starting_location: (usize, usize),
-	increment: (isize, isize),
-	include_resonant_harmonics: bool,
-) -> HashSet<(usize, usize)> {
-	let mut antinodes = HashSet::new();
-
-	if include_resonant_harmonics {
-		antinodes.insert(starting_location);
+	grid: &Grid<char>,
+	starting_location: Position,
+	increment: Point,
+) -> HashSet<Position> {
+	let mut antinodes = HashSet::from([starting_location]);
+	let mut candidate = starting_location;
+
+	while let Some(next) = grid.offset(candidate, increment) {
+		cancel::checkpoint();
+		candidate = next;
+		antinodes.insert(candidate);
 	}
 
-	let (mut x_cand, mut y_cand) = (starting_location.0 as isize, starting_location.1 as isize);
-
-	loop {
-		x_cand += increment.0;
-		y_cand += increment.1;
-
-		if x_cand < 0 || x_cand >= width as isize || y_cand < 0 || y_cand >= height as isize {
-			break;
-		}
-
-		antinodes.insert((x_cand as usize, y_cand as usize));
+	antinodes
+}
 
-		if !include_resonant_harmonics {
-			break;
-		}
+/// Greatest common divisor
+fn gcd(a: usize, b: usize) -> usize {
+	if b == 0 {
+		a
+	} else {
+		gcd(b, a % b)
 	}
-
-	antinodes
 }
 
 /// Determine all antinodes for all antennae of all frequencies
 fn determine_all_antinodes(
-	width: usize,
-	height: usize,
-	antennae_locations: &HashMap<char, HashSet<(usize, usize)>>,
+	grid: &Grid<char>,
+	antennae_locations: &AntennaeLocations,
 	include_resonant_harmonics: bool,
-) -> HashSet<(usize, usize)> {
+) -> HashSet<Position> {
 	let mut all_antinodes = HashSet::new();
 
-	for locations in antennae_locations.values() {
-		let antinodes = determine_antinodes(width, height, locations, include_resonant_harmonics);
+	for (frequency, locations) in antennae_locations {
+		let antinodes = determine_antinodes(grid, locations, include_resonant_harmonics);
+		trace!(
+			Info,
+			"frequency",
+			frequency = frequency,
+			antennae = locations.len(),
+			antinodes = antinodes.len()
+		);
 		all_antinodes.extend(antinodes);
 	}
 
 	all_antinodes
 }
+
+/// Generate a square map with about one antenna in 12 positions
+fn generate_antennae(rng: &mut Rng, width: usize) -> String {
+	const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+	let mut cells = vec![b'.'; width * width];
+	for _ in 0..cells.len() / 12 {
+		let index = rng.below(cells.len());
+		cells[index] = *rng.choose(FREQUENCIES);
+	}
+
+	let mut content = String::with_capacity((width + 1) * width);
+	for row in cells.chunks(width) {
+		content.push_str(&String::from_utf8_lossy(row));
+		content.push('\n');
+	}
+
+	content
+}
@@ -0,0 +1,63 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+use super::Harmonics;
+
+/// The size of the map and the frequency and position of every antenna
+fn antennae(input: &str) -> (isize, isize, Vec<(char, isize, isize)>) {
+	let lines = input
+		.lines()
+		.filter(|line| !line.is_empty())
+		.collect::<Vec<_>>();
+	let mut antennae = Vec::new();
+	for (y, line) in lines.iter().enumerate() {
+		for (x, frequency) in line.chars().enumerate() {
+			if frequency != '.' {
+				antennae.push((frequency, x as isize, y as isize));
+			}
+		}
+	}
+	(lines[0].len() as isize, lines.len() as isize, antennae)
+}
+
+/// Check every position against every pair of antennae of the same frequency
+///
+/// Without harmonics a position is an antinode if it is in line with both antennae and one of them
+/// is twice as far away as the other, on the same side or between them. With harmonics being in
+/// line is enough.
+fn count_antinodes(input: &str, include_resonant_harmonics: bool) -> usize {
+	let (width, height, antennae) = antennae(input);
+	let mut count = 0;
+
+	for y in 0..height {
+		for x in 0..width {
+			let is_antinode = antennae.iter().any(|(frequency, ax, ay)| {
+				antennae.iter().any(|(other, bx, by)| {
+					if other != frequency || (ax, ay) == (bx, by) {
+						return false;
+					}
+					let (to_a, to_b) = ((ax - x, ay - y), (bx - x, by - y));
+					if include_resonant_harmonics {
+						to_a.0 * to_b.1 == to_a.1 * to_b.0
+					} else {
+						to_a == (2 * to_b.0, 2 * to_b.1) || to_a == (-2 * to_b.0, -2 * to_b.1)
+					}
+				})
+			});
+			if is_antinode {
+				count += 1;
+			}
+		}
+	}
+
+	count
+}
+
+/// Count the antinodes of the first part
+pub fn part_one(input: &str, harmonics: &Harmonics) -> usize {
+	count_antinodes(input, harmonics.part_one)
+}
+
+/// Count the antinodes of the second part
+pub fn part_two(input: &str, harmonics: &Harmonics) -> usize {
+	count_antinodes(input, harmonics.part_two)
+}
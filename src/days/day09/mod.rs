@@ -1,42 +1,94 @@
-use std::fs;
+mod reference;
+
+use crate::{
+	cancel,
+	error::ParseError,
+	rng::Rng,
+	scan::Scanner,
+	solution::{Example, InputSize, Solution},
+	trace,
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+	type Input = Vec<usize>;
+	type PartOne = u128;
+	type PartTwo = u128;
+	type Params = ();
+
+	const EXAMPLES: &'static [Example] = &[Example {
+		input: include_str!("example"),
+		part_one: Some("1928"),
+		part_two: Some("2858"),
+	}];
+
+	const INPUT_SIZE: InputSize = InputSize {
+		unit: "digits",
+		default: 19999,
+		small: 30,
+	};
+
+	fn parse(input: &str) -> Result<Self::Input, ParseError> {
+		read_disk_map(input)
+	}
+
+	fn part_one(disk_map: &Self::Input, _params: &()) -> u128 {
+		let blocks = to_blocks(disk_map);
+		let compacted_blocks = compact_blocks(&blocks);
+		compacted_blocks_checksum(&compacted_blocks)
+	}
 
-pub fn main() {
-	let disk_map = read_input();
-	let blocks = to_blocks(&disk_map);
-	let compacted_blocks = compact_blocks(&blocks);
-	let checksum = compacted_blocks_checksum(&compacted_blocks);
+	fn part_two(disk_map: &Self::Input, _params: &()) -> u128 {
+		let blocks_v2 = to_blocks_v2(disk_map);
+		let defragmented_files = defragment_files(&blocks_v2);
+		compacted_files_checksum(&defragmented_files)
+	}
 
-	println!("Checksum: {}", checksum);
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		generate_disk_map(rng, size)
+	}
 
-	let blocks_v2 = to_blocks_v2(&disk_map);
-	let defragmented_files = defragment_files(&blocks_v2);
-	let checksum_v2 = compacted_files_checksum(&defragmented_files);
+	fn reference_one(input: &str, _params: &()) -> u128 {
+		reference::part_one(input)
+	}
 
-	println!("Checksum v2: {}", checksum_v2);
+	fn reference_two(input: &str, _params: &()) -> u128 {
+		reference::part_two(input)
+	}
 }
 
-/// Read and parse the input file
-fn read_input() -> Vec<usize> {
-	let content = fs::read_to_string("./src/days/day09/input").expect("Unable to read file.");
+/// Checksum after compacting the disk block by block
+pub fn part_one(input: &str) -> Result<u128, ParseError> {
+	Ok(Day09::part_one(&Day09::parse_input(input)?, &()))
+}
 
+/// Checksum after compacting the disk file by file
+pub fn part_two(input: &str) -> Result<u128, ParseError> {
+	Ok(Day09::part_two(&Day09::parse_input(input)?, &()))
+}
+
+/// Parse the input into the disk map
+fn read_disk_map(content: &str) -> Result<Vec<usize>, ParseError> {
 	let mut disk_map: Vec<usize> = Vec::new();
 
-	for (y, line) in content.lines().enumerate() {
-		if line.is_empty() {
-			break;
-		}
+	// every digit is a usize to be added
+	let mut scanner = Scanner::new(content.as_bytes());
+	while !scanner.at_line_end() {
+		disk_map.push(usize::from(scanner.digit()?));
+	}
 
-		// every char is a usize to be added
-		for c in line.chars() {
-			disk_map.push(c.to_string().parse::<usize>().unwrap());
-		}
+	// only empty lines may follow the disk map
+	scanner.skip(b"\n");
+	if !scanner.is_empty() {
+		return Err(scanner.error("end of input"));
 	}
 
-	disk_map
+	Ok(disk_map)
 }
 
 /// Convert to blocks
-fn to_blocks(disk_map: &Vec<usize>) -> Vec<Option<usize>> {
+fn to_blocks(disk_map: &[usize]) -> Vec<Option<usize>> {
 	let mut blocks: Vec<Option<usize>> = Vec::new();
 
 	for (id_2, length) in disk_map.iter().enumerate() {
@@ -56,11 +108,12 @@ fn to_blocks(disk_map: &Vec<usize>) -> Vec<Option<usize>> {
 }
 
 /// Compact Blocks
-fn compact_blocks(blocks: &Vec<Option<usize>>) -> Vec<usize> {
+fn compact_blocks(blocks: &[Option<usize>]) -> Vec<usize> {
 	let mut compacted_blocks: Vec<usize> = Vec::new();
-	let mut blocks = blocks.clone();
+	let mut blocks = blocks.to_vec();
 
 	while !blocks.is_empty() {
+		cancel::checkpoint();
 		match blocks.remove(0) {
 			Some(id) => {
 				compacted_blocks.push(id);
@@ -68,6 +121,7 @@ fn compact_blocks(blocks: &Vec<Option<usize>>) -> Vec<usize> {
 			None => loop {
 				match blocks.pop() {
 					Some(Some(id)) => {
+						trace!(Debug, "block moved", id = id, to = compacted_blocks.len());
 						compacted_blocks.push(id);
 						break;
 					}
@@ -86,11 +140,14 @@ fn compact_blocks(blocks: &Vec<Option<usize>>) -> Vec<usize> {
 }
 
 /// compacted blocks checksum
-fn compacted_blocks_checksum(compacted_blocks: &Vec<usize>) -> usize {
+///
+/// Computed in `u128`: the checksums of disk maps with millions of digits exceed 64 bits, while
+/// a disk map long enough to overflow 128 bits would not fit in memory.
+fn compacted_blocks_checksum(compacted_blocks: &[usize]) -> u128 {
 	let mut checksum = 0;
 
 	for (position, id) in compacted_blocks.iter().enumerate() {
-		checksum += position * id;
+		checksum += position as u128 * *id as u128;
 	}
 
 	checksum
@@ -110,7 +167,7 @@ enum Block {
 }
 
 /// convert to blocks: Block
-fn to_blocks_v2(disk_map: &Vec<usize>) -> Vec<Block> {
+fn to_blocks_v2(disk_map: &[usize]) -> Vec<Block> {
 	let mut blocks: Vec<Block> = Vec::new();
 	let mut start = 0;
 
@@ -131,16 +188,13 @@ fn to_blocks_v2(disk_map: &Vec<usize>) -> Vec<Block> {
 }
 
 /// defragement the files (entire file moves, not just blocks)
-fn defragment_files(blocks: &Vec<Block>) -> Vec<File> {
+fn defragment_files(blocks: &[Block]) -> Vec<File> {
 	let mut defragmented_files: Vec<File> = Vec::new();
 
 	let mut free_blocks: Vec<FreeBlock> = Vec::new();
 	for block in blocks.iter() {
-		match block {
-			Block::FreeBlock(free_block) => {
-				free_blocks.push(free_block.clone());
-			}
-			_ => {}
+		if let Block::FreeBlock(free_block) = block {
+			free_blocks.push(free_block.clone());
 		}
 	}
 
@@ -154,7 +208,16 @@ fn defragment_files(blocks: &Vec<Block>) -> Vec<File> {
 				let mut file_to_add = file.clone();
 
 				for free_block in free_blocks.iter_mut() {
+					cancel::checkpoint();
 					if free_block.0 < start && free_block.1 >= length {
+						trace!(
+							Info,
+							"file moved",
+							id = id,
+							from = start,
+							to = free_block.0,
+							length = length
+						);
 						file_to_add = File(id, free_block.0, length);
 
 						free_block.0 += length;
@@ -175,19 +238,36 @@ fn defragment_files(blocks: &Vec<Block>) -> Vec<File> {
 	defragmented_files
 }
 
-fn compacted_files_checksum(defragmented_files: &Vec<File>) -> usize {
+/// compacted files checksum, in `u128` like [`compacted_blocks_checksum`]
+fn compacted_files_checksum(defragmented_files: &[File]) -> u128 {
 	let mut checksum = 0;
 
 	for file in defragmented_files.iter() {
 		let id = file.0;
-		let mut start = file.1;
+		let start = file.1;
 		let length = file.2;
 
-		for _ in 0..length {
-			checksum += start * id;
-			start += 1;
+		for position in start..start + length {
+			checksum += position as u128 * id as u128;
 		}
 	}
 
 	checksum
 }
+
+/// Generate a disk map of `digits` digits, where every file takes at least one block
+fn generate_disk_map(rng: &mut Rng, digits: usize) -> String {
+	let mut content = String::with_capacity(digits + 1);
+
+	for index in 0..digits {
+		let length = if index % 2 == 0 {
+			rng.range(1..=9)
+		} else {
+			rng.range(0..=9)
+		};
+		content.push(char::from(b'0' + length as u8));
+	}
+
+	content.push('\n');
+	content
+}
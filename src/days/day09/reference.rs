@@ -0,0 +1,62 @@
+//! Naive reference solutions, see [`crate::solution::Solution::reference_one`]
+
+/// The file id of every block, `None` for free blocks
+fn blocks(input: &str) -> Vec<Option<usize>> {
+	let mut blocks = Vec::new();
+	for (index, length) in input.trim_end().bytes().enumerate() {
+		let id = (index % 2 == 0).then_some(index / 2);
+		blocks.extend(std::iter::repeat_n(id, (length - b'0') as usize));
+	}
+	blocks
+}
+
+fn checksum(blocks: &[Option<usize>]) -> u128 {
+	blocks
+		.iter()
+		.enumerate()
+		.map(|(position, id)| position as u128 * id.unwrap_or(0) as u128)
+		.sum()
+}
+
+/// Move the last file block into the first free block until no free block is left of a file block
+pub fn part_one(input: &str) -> u128 {
+	let mut blocks = blocks(input);
+	loop {
+		let free = blocks.iter().position(Option::is_none);
+		let last = blocks.iter().rposition(Option::is_some);
+		match (free, last) {
+			(Some(free), Some(last)) if free < last => blocks.swap(free, last),
+			_ => break,
+		}
+	}
+	checksum(&blocks)
+}
+
+/// Move every file, highest id first, to the leftmost span of free blocks it fits in
+pub fn part_two(input: &str) -> u128 {
+	let mut blocks = blocks(input);
+	let highest = blocks.iter().flatten().max().copied().unwrap_or(0);
+
+	for id in (0..=highest).rev() {
+		let Some(start) = blocks.iter().position(|block| *block == Some(id)) else {
+			continue;
+		};
+		let length = blocks[start..]
+			.iter()
+			.take_while(|block| **block == Some(id))
+			.count();
+
+		let target = (0..start).find(|candidate| {
+			blocks[*candidate..*candidate + length]
+				.iter()
+				.all(Option::is_none)
+		});
+		if let Some(target) = target {
+			for offset in 0..length {
+				blocks.swap(start + offset, target + offset);
+			}
+		}
+	}
+
+	checksum(&blocks)
+}
@@ -1,3 +1,4 @@
+// `aoc new-day` adds new days here and to the `days!` list below
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -7,3 +8,95 @@ pub mod day06;
 pub mod day07;
 pub mod day08;
 pub mod day09;
+
+use std::{any::Any, fmt};
+
+use crate::{error::Error, params::Overrides, solution::DynSolution};
+
+/// One of the two parts of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+	One,
+	Two,
+}
+
+impl Part {
+	/// Both parts, in order
+	pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+	/// The parts matching a selection, where `None` selects both parts
+	pub fn selected(selection: Option<Part>) -> impl Iterator<Item = Part> {
+		Part::ALL
+			.into_iter()
+			.filter(move |part| selection.is_none_or(|selected| selected == *part))
+	}
+}
+
+impl fmt::Display for Part {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match self {
+			Part::One => "1",
+			Part::Two => "2",
+		})
+	}
+}
+
+/// A registered day and its solution
+pub struct Day {
+	pub number: u8,
+	pub solution: &'static dyn DynSolution,
+}
+
+impl Day {
+	/// Parse the input of this day
+	pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, Error> {
+		self.solution.parse(input).map_err(|error| Error::Parse {
+			day: self.number,
+			error,
+		})
+	}
+
+	/// Resolve the parameters of this day, overridden where given
+	///
+	/// Overrides of parameters this day does not declare are ignored.
+	pub fn configure(&self, overrides: &Overrides) -> Result<Box<dyn Any>, Error> {
+		self.solution
+			.configure(overrides)
+			.map_err(|message| Error::Param {
+				day: self.number,
+				message,
+			})
+	}
+}
+
+/// Register the solutions of the day modules in [`DAYS`]
+///
+/// The modules are declared outside of the macro, where `cargo fmt` finds them.
+macro_rules! days {
+	($($number:literal => $module:ident::$solution:ident,)*) => {
+		/// All implemented days, ordered by day number
+		pub const DAYS: &[Day] = &[$(Day {
+			number: $number,
+			solution: &$module::$solution,
+		}),*];
+	};
+}
+
+days! {
+	1 => day01::Day01,
+	2 => day02::Day02,
+	3 => day03::Day03,
+	4 => day04::Day04,
+	5 => day05::Day05,
+	6 => day06::Day06,
+	7 => day07::Day07,
+	8 => day08::Day08,
+	9 => day09::Day09,
+}
+
+/// Look up a registered day by its number
+pub fn get(number: u8) -> Result<&'static Day, Error> {
+	DAYS.iter()
+		.find(|day| day.number == number)
+		.ok_or(Error::UnknownDay(number))
+}
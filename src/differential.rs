@@ -0,0 +1,173 @@
+//! Differential testing of the solutions against their naive reference solutions
+//!
+//! Random inputs from [`Solution::generate`](crate::Solution::generate) are solved by both the
+//! solution and its reference solution. When the answers differ, the input is shrunk to the
+//! smallest input that still shows the same disagreement.
+
+use std::{
+	any::Any,
+	fmt, mem,
+	panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+	days::{Day, Part},
+	error::Error,
+	params::Overrides,
+	rng::Rng,
+	runner::panic_message,
+};
+
+/// How a solution handled an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+	Solved(String),
+	/// The input was rejected by the parser
+	Rejected(String),
+	Panicked(String),
+}
+
+impl fmt::Display for Answer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Answer::Solved(answer) => write!(f, "{}", answer),
+			Answer::Rejected(error) => write!(f, "rejected the input: {}", error),
+			Answer::Panicked(message) => write!(f, "panicked: {}", message),
+		}
+	}
+}
+
+/// A generated input the solution and its reference solution disagree on
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+	pub part: Part,
+	/// The seed and size the input was generated with
+	pub seed: u64,
+	pub size: usize,
+	/// The smallest input derived from the generated one with the same disagreement
+	pub input: String,
+	pub answer: Answer,
+	pub reference: Answer,
+}
+
+/// The result of checking a day against its reference solution
+#[derive(Debug, Clone)]
+pub struct DayCheck {
+	pub day: u8,
+	/// The number of generated inputs both solutions agreed on
+	pub agreed: usize,
+	/// The first disagreement, after which the check stopped
+	pub disagreement: Option<Disagreement>,
+}
+
+/// Solve `seeds` generated inputs with the solution and the reference solution of a day
+///
+/// The input of seed `n` has size `1 + n % small`, with `small` from
+/// [`InputSize`](crate::solution::InputSize), so the sizes cycle through the small sizes.
+pub fn check_day(
+	day: &Day,
+	part: Option<Part>,
+	seeds: u64,
+	overrides: &Overrides,
+) -> Result<DayCheck, Error> {
+	let params = day.configure(overrides)?;
+	let small = day.solution.input_size().small as u64;
+	let mut agreed = 0;
+
+	for seed in 0..seeds {
+		let size = (1 + seed % small) as usize;
+		let input = day.solution.generate(&mut Rng::new(seed), size);
+
+		for part in Part::selected(part) {
+			let (answer, reference) = solve(day, &input, params.as_ref(), part);
+			if answer == reference {
+				continue;
+			}
+
+			// keep the kind of disagreement, so e.g. a panic does not shrink into a rejected input
+			let still_disagrees = |candidate: &str| {
+				let (candidate_answer, candidate_reference) =
+					solve(day, candidate, params.as_ref(), part);
+				candidate_answer != candidate_reference
+					&& mem::discriminant(&candidate_answer) == mem::discriminant(&answer)
+					&& mem::discriminant(&candidate_reference) == mem::discriminant(&reference)
+			};
+			let input = shrink(input, still_disagrees);
+			let (answer, reference) = solve(day, &input, params.as_ref(), part);
+
+			return Ok(DayCheck {
+				day: day.number,
+				agreed,
+				disagreement: Some(Disagreement {
+					part,
+					seed,
+					size,
+					input,
+					answer,
+					reference,
+				}),
+			});
+		}
+		agreed += 1;
+	}
+
+	Ok(DayCheck {
+		day: day.number,
+		agreed,
+		disagreement: None,
+	})
+}
+
+/// Solve a part of an input with the solution and with the reference solution
+fn solve(day: &Day, input: &str, params: &dyn Any, part: Part) -> (Answer, Answer) {
+	let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+		day.solution
+			.parse(input)
+			.map(|parsed| day.solution.solve(parsed.as_ref(), params, part))
+	}));
+	let answer = match answer {
+		Ok(Ok(answer)) => Answer::Solved(answer),
+		Ok(Err(error)) => Answer::Rejected(error.to_string()),
+		Err(payload) => Answer::Panicked(panic_message(payload.as_ref())),
+	};
+
+	let reference = panic::catch_unwind(AssertUnwindSafe(|| {
+		day.solution.reference(input, params, part)
+	}));
+	let reference = match reference {
+		Ok(answer) => Answer::Solved(answer),
+		Err(payload) => Answer::Panicked(panic_message(payload.as_ref())),
+	};
+
+	(answer, reference)
+}
+
+/// Remove lines, then single characters, as long as the input still fails
+fn shrink(mut input: String, still_fails: impl Fn(&str) -> bool) -> String {
+	loop {
+		let lines = input.split_inclusive('\n').collect::<Vec<_>>();
+		let without_line = (0..lines.len())
+			.map(|removed| {
+				let mut candidate = lines.clone();
+				candidate.remove(removed);
+				candidate.concat()
+			})
+			.find(|candidate| still_fails(candidate));
+
+		let shrunk = without_line.or_else(|| {
+			input
+				.char_indices()
+				.map(|(index, c)| {
+					let mut candidate = input.clone();
+					candidate.replace_range(index..index + c.len_utf8(), "");
+					candidate
+				})
+				.find(|candidate| still_fails(candidate))
+		});
+
+		match shrunk {
+			Some(shrunk) => input = shrunk,
+			None => return input,
+		}
+	}
+}
@@ -0,0 +1,150 @@
+use std::{fmt, io, path::PathBuf, process::ExitCode};
+
+/// A malformed puzzle input, located by line and column (both starting at 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	pub line: usize,
+	pub column: usize,
+	pub expected: String,
+	pub found: String,
+}
+
+impl ParseError {
+	/// Error for the byte at `offset` of the input, or for the end of the input if `offset` is past it
+	pub fn at(input: &[u8], offset: usize, expected: impl Into<String>) -> Self {
+		let offset = offset.min(input.len());
+		let before = &input[..offset];
+		let line = before.iter().filter(|byte| **byte == b'\n').count() + 1;
+		let line_start = before
+			.iter()
+			.rposition(|byte| *byte == b'\n')
+			.map_or(0, |newline| newline + 1);
+		let column = String::from_utf8_lossy(&input[line_start..offset])
+			.chars()
+			.count() + 1;
+
+		let found = match String::from_utf8_lossy(&input[offset..]).chars().next() {
+			None => "end of input".to_string(),
+			Some('\n') => "end of line".to_string(),
+			Some(c) => format!("`{}`", c.escape_debug()),
+		};
+
+		ParseError {
+			line,
+			column,
+			expected: expected.into(),
+			found,
+		}
+	}
+
+	/// Error for `token`, which has to be a slice of `input`
+	pub fn at_token(input: &str, token: &str, expected: impl Into<String>) -> Self {
+		let offset = token.as_ptr() as usize - input.as_ptr() as usize;
+		let mut error = ParseError::at(input.as_bytes(), offset, expected);
+		if !token.is_empty() {
+			error.found = format!("`{}`", token.escape_debug());
+		}
+		error
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"line {}, column {}: expected {}, found {}",
+			self.line, self.column, self.expected, self.found
+		)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// Any error surfaced by the command line runner
+#[derive(Debug)]
+pub enum Error {
+	/// The command line arguments are invalid
+	Usage(String),
+	/// The requested day is not registered
+	UnknownDay(u8),
+	/// An input could not be read
+	Io(io::Error),
+	/// The input of a day is malformed
+	Parse { day: u8, error: ParseError },
+	/// The answers file is malformed
+	Answers { path: PathBuf, error: ParseError },
+	/// Some answers did not match the expected answers
+	Verification { failed: usize },
+	/// The benchmark baseline file is malformed
+	Baseline { path: PathBuf, error: ParseError },
+	/// Some stages got slower than their baseline, after the comparisons were reported
+	Regressed { stages: usize },
+	/// A new day could not be created
+	Scaffold(String),
+	/// A parameter of a day has an invalid value
+	Param { day: u8, message: String },
+	/// Some days of a run failed, after their errors were reported
+	Failed { days: usize },
+	/// The solution of a day panicked
+	Panicked { day: u8, message: String },
+	/// Some inputs of a batch run failed, after their errors were reported
+	BatchFailed { inputs: usize },
+	/// Some days disagreed with their reference solutions, after the disagreements were reported
+	Disagreed { days: usize },
+	/// Some days failed on fuzzed inputs, after the inputs were reported
+	Crashed { days: usize },
+}
+
+impl Error {
+	/// The exit code the process should terminate with
+	pub fn exit_code(&self) -> ExitCode {
+		match self {
+			Error::Usage(_) | Error::Param { .. } => ExitCode::from(2),
+			_ => ExitCode::FAILURE,
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Usage(message) => write!(f, "{}", message),
+			Error::UnknownDay(day) => write!(f, "day {} is not implemented", day),
+			Error::Io(error) => write!(f, "{}", error),
+			Error::Parse { day, error } => write!(f, "day {}, {}", day, error),
+			Error::Answers { path, error } => write!(f, "{}, {}", path.display(), error),
+			Error::Verification { failed } => write!(f, "{} answers did not verify", failed),
+			Error::Baseline { path, error } => write!(f, "{}, {}", path.display(), error),
+			Error::Regressed { stages } => {
+				write!(f, "{} stages regressed against the baseline", stages)
+			}
+			Error::Scaffold(message) => write!(f, "{}", message),
+			Error::Param { day, message } => write!(f, "day {}, {}", day, message),
+			Error::Failed { days } => write!(f, "{} days failed", days),
+			Error::Panicked { day, message } => write!(f, "day {} panicked: {}", day, message),
+			Error::BatchFailed { inputs } => write!(f, "{} inputs failed", inputs),
+			Error::Disagreed { days } => {
+				write!(f, "{} days disagreed with their reference solutions", days)
+			}
+			Error::Crashed { days } => write!(f, "{} days failed on fuzzed inputs", days),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Io(error) => Some(error),
+			Error::Parse { error, .. }
+			| Error::Answers { error, .. }
+			| Error::Baseline { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(error: io::Error) -> Self {
+		Error::Io(error)
+	}
+}
@@ -0,0 +1,214 @@
+//! Fuzzing of the parsers and solvers with mutated inputs
+//!
+//! Every day has to handle arbitrary input: the parser either rejects it with a [`ParseError`] or
+//! both parts solve it, without panicking, overflowing or looping forever. Inputs are derived
+//! from the examples and from generated inputs by random byte level mutations. Arithmetic overflow
+//! only panics in builds with overflow checks, such as the debug and test profiles.
+//!
+//! [`ParseError`]: crate::ParseError
+
+use std::{
+	fmt,
+	panic::{self, AssertUnwindSafe},
+	sync::mpsc,
+	thread,
+	time::Duration,
+};
+
+use crate::{
+	cancel::{self, Cancelled},
+	days::{Day, Part},
+	params::Overrides,
+	rng::Rng,
+	runner::panic_message,
+};
+
+/// Bytes the mutations insert, the ones that are meaningful in at least one input format
+const BYTES: &[u8] = b"0123456789 \n\r\t.#^,|:+*-()mul'dont\0\xff";
+
+/// How long after its time limit an input is given up on if its solver does not stop at the
+/// deadline, because it hangs in a loop without a checkpoint
+const GRACE: Duration = Duration::from_secs(1);
+
+/// Numbers the mutations insert, picked to hit edge cases of parsers and arithmetic
+const NUMBERS: &[&str] = &[
+	"0",
+	"1",
+	"00",
+	"255",
+	"65536",
+	"4294967296",
+	"18446744073709551615",
+	"18446744073709551616",
+	"99999999999999999999999",
+];
+
+/// How a day failed on an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+	Panicked(String),
+	/// Parsing and solving took longer than the time limit
+	TimedOut,
+}
+
+impl fmt::Display for Failure {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Failure::Panicked(message) => write!(f, "panicked: {}", message),
+			Failure::TimedOut => write!(f, "timed out"),
+		}
+	}
+}
+
+/// An input a day failed on
+#[derive(Debug, Clone)]
+pub struct Crash {
+	pub day: u8,
+	/// The number of inputs the day handled before
+	pub handled: usize,
+	pub input: Vec<u8>,
+	pub failure: Failure,
+}
+
+/// Settings of a fuzzing run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fuzzing {
+	/// The number of mutated inputs per day
+	pub iterations: usize,
+	pub seed: u64,
+	/// The time limit of parsing and solving one input
+	pub time_limit: Duration,
+}
+
+impl Default for Fuzzing {
+	fn default() -> Self {
+		Fuzzing {
+			iterations: 10_000,
+			seed: 0,
+			time_limit: Duration::from_secs(5),
+		}
+	}
+}
+
+/// Parse and solve mutated inputs until the day fails on one or all iterations are done
+///
+/// Returns the number of inputs handled, or the failing input. The inputs are handled on a worker
+/// thread with a deadline, see [`cancel::with_deadline`], so a hanging solver stops at its next
+/// checkpoint. A worker that misses its deadline anyway is abandoned.
+pub fn fuzz_day(day: &'static Day, fuzzing: &Fuzzing) -> Result<usize, Crash> {
+	let mut rng = Rng::new(fuzzing.seed);
+	let corpus = corpus(day, &mut rng);
+
+	let (inputs, worker_inputs) = mpsc::channel::<Vec<u8>>();
+	let (worker_results, results) = mpsc::channel();
+	let time_limit = fuzzing.time_limit;
+	thread::spawn(move || {
+		let params = day
+			.configure(&Overrides::default())
+			.expect("the default parameters are valid");
+		for input in worker_inputs {
+			let result = panic::catch_unwind(AssertUnwindSafe(|| {
+				cancel::with_deadline(Some(time_limit), || {
+					let input = String::from_utf8_lossy(&input);
+					if let Ok(parsed) = day.solution.parse(&input) {
+						for part in Part::ALL {
+							day.solution.solve(parsed.as_ref(), params.as_ref(), part);
+						}
+					}
+				})
+			}));
+			let result = result.map_err(|payload| {
+				if payload.is::<Cancelled>() {
+					Failure::TimedOut
+				} else {
+					Failure::Panicked(panic_message(payload.as_ref()))
+				}
+			});
+			if worker_results.send(result).is_err() {
+				break;
+			}
+		}
+	});
+
+	for handled in 0..fuzzing.iterations {
+		let mut input = rng.choose(&corpus).clone();
+		for _ in 0..rng.range(1..=4) {
+			mutate(&mut rng, &mut input);
+		}
+
+		inputs
+			.send(input.clone())
+			.expect("the worker runs until the inputs end");
+		let failure = match results.recv_timeout(time_limit + GRACE) {
+			Ok(Ok(())) => continue,
+			Ok(Err(failure)) => failure,
+			Err(_) => Failure::TimedOut,
+		};
+		return Err(Crash {
+			day: day.number,
+			handled,
+			input,
+			failure,
+		});
+	}
+
+	Ok(fuzzing.iterations)
+}
+
+/// The inputs mutations start from: nothing, the examples and small generated inputs
+fn corpus(day: &Day, rng: &mut Rng) -> Vec<Vec<u8>> {
+	let mut corpus = vec![Vec::new()];
+	for example in day.solution.examples() {
+		corpus.push(example.input.as_bytes().to_vec());
+	}
+	let small = day.solution.input_size().small;
+	for size in 1..=small.min(8) {
+		corpus.push(day.solution.generate(rng, size).into_bytes());
+	}
+	corpus
+}
+
+/// Apply one random mutation
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>) {
+	let position = rng.below(input.len() + 1);
+	match rng.below(8) {
+		// replace a byte
+		0 if position < input.len() => input[position] = *rng.choose(BYTES),
+		// insert a byte
+		1 => input.insert(position, *rng.choose(BYTES)),
+		// remove a byte
+		2 if position < input.len() => {
+			input.remove(position);
+		}
+		// cut off the end
+		3 => input.truncate(position),
+		// remove a range
+		4 => {
+			let end = rng.range(position..=input.len());
+			input.drain(position..end);
+		}
+		// repeat a range
+		5 => {
+			let end = rng.range(position..=input.len().min(position + 64));
+			let copy = input[position..end].to_vec();
+			input.splice(position..position, copy);
+		}
+		// insert a number
+		6 => {
+			let number = rng.choose(NUMBERS).bytes();
+			input.splice(position..position, number);
+		}
+		// remove a line
+		_ => {
+			let start = input[..position]
+				.iter()
+				.rposition(|byte| *byte == b'\n')
+				.map_or(0, |newline| newline + 1);
+			let end = input[position..]
+				.iter()
+				.position(|byte| *byte == b'\n')
+				.map_or(input.len(), |newline| position + newline + 1);
+			input.drain(start..end);
+		}
+	}
+}
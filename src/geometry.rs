@@ -0,0 +1,271 @@
+use std::{
+	fmt,
+	ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::grid::Position;
+
+/// A signed point or vector as (x, y), with x growing to the right and y growing downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+	pub x: isize,
+	pub y: isize,
+}
+
+impl Point {
+	pub const ORIGIN: Point = Point::new(0, 0);
+
+	/// Steps to the 4 orthogonal neighbours, clockwise starting upwards
+	pub const ORTHOGONAL: [Point; 4] = [
+		Point::new(0, -1),
+		Point::new(1, 0),
+		Point::new(0, 1),
+		Point::new(-1, 0),
+	];
+
+	/// Steps to the 4 diagonal neighbours, clockwise starting up-right
+	pub const DIAGONAL: [Point; 4] = [
+		Point::new(1, -1),
+		Point::new(1, 1),
+		Point::new(-1, 1),
+		Point::new(-1, -1),
+	];
+
+	/// Steps to all 8 neighbours, clockwise starting upwards
+	pub const NEIGHBOURS: [Point; 8] = [
+		Point::new(0, -1),
+		Point::new(1, -1),
+		Point::new(1, 0),
+		Point::new(1, 1),
+		Point::new(0, 1),
+		Point::new(-1, 1),
+		Point::new(-1, 0),
+		Point::new(-1, -1),
+	];
+
+	pub const fn new(x: isize, y: isize) -> Self {
+		Point { x, y }
+	}
+
+	/// The 4 orthogonal neighbours of this point
+	pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Point> {
+		Point::ORTHOGONAL.into_iter().map(move |step| self + step)
+	}
+
+	/// All 8 neighbours of this point
+	pub fn neighbours(self) -> impl Iterator<Item = Point> {
+		Point::NEIGHBOURS.into_iter().map(move |step| self + step)
+	}
+
+	/// The point as grid coordinates, or `None` if either coordinate is negative
+	pub fn to_position(self) -> Option<Position> {
+		Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+	}
+
+	/// The point rotated by 90 degrees clockwise around the origin
+	pub fn rotate_right(self) -> Self {
+		Point::new(-self.y, self.x)
+	}
+
+	/// The point rotated by 90 degrees counterclockwise around the origin
+	pub fn rotate_left(self) -> Self {
+		Point::new(self.y, -self.x)
+	}
+
+	/// The manhattan distance to the origin
+	pub fn manhattan(self) -> usize {
+		self.x.unsigned_abs() + self.y.unsigned_abs()
+	}
+}
+
+impl From<Position> for Point {
+	/// Grid coordinates as a point
+	///
+	/// Positions within a grid always fit, as a grid cannot hold more than `isize::MAX` cells.
+	fn from((x, y): Position) -> Self {
+		Point::new(x as isize, y as isize)
+	}
+}
+
+impl fmt::Display for Point {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "({}, {})", self.x, self.y)
+	}
+}
+
+impl Add for Point {
+	type Output = Point;
+
+	fn add(self, other: Point) -> Point {
+		Point::new(self.x + other.x, self.y + other.y)
+	}
+}
+
+impl AddAssign for Point {
+	fn add_assign(&mut self, other: Point) {
+		*self = *self + other;
+	}
+}
+
+impl Sub for Point {
+	type Output = Point;
+
+	fn sub(self, other: Point) -> Point {
+		Point::new(self.x - other.x, self.y - other.y)
+	}
+}
+
+impl SubAssign for Point {
+	fn sub_assign(&mut self, other: Point) {
+		*self = *self - other;
+	}
+}
+
+impl Neg for Point {
+	type Output = Point;
+
+	fn neg(self) -> Point {
+		Point::new(-self.x, -self.y)
+	}
+}
+
+impl Mul<isize> for Point {
+	type Output = Point;
+
+	fn mul(self, factor: isize) -> Point {
+		Point::new(self.x * factor, self.y * factor)
+	}
+}
+
+/// One of the 4 orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+	Up,
+	Right,
+	Down,
+	Left,
+}
+
+impl Direction {
+	/// All directions, clockwise starting upwards
+	pub const ALL: [Direction; 4] = [
+		Direction::Up,
+		Direction::Right,
+		Direction::Down,
+		Direction::Left,
+	];
+
+	/// The step of a single move in this direction
+	pub fn step(self) -> Point {
+		match self {
+			Direction::Up => Point::new(0, -1),
+			Direction::Right => Point::new(1, 0),
+			Direction::Down => Point::new(0, 1),
+			Direction::Left => Point::new(-1, 0),
+		}
+	}
+
+	/// The direction after turning 90 degrees clockwise
+	pub fn turn_right(self) -> Self {
+		match self {
+			Direction::Up => Direction::Right,
+			Direction::Right => Direction::Down,
+			Direction::Down => Direction::Left,
+			Direction::Left => Direction::Up,
+		}
+	}
+
+	/// The direction after turning 90 degrees counterclockwise
+	pub fn turn_left(self) -> Self {
+		match self {
+			Direction::Up => Direction::Left,
+			Direction::Left => Direction::Down,
+			Direction::Down => Direction::Right,
+			Direction::Right => Direction::Up,
+		}
+	}
+
+	/// The opposite direction
+	pub fn reverse(self) -> Self {
+		match self {
+			Direction::Up => Direction::Down,
+			Direction::Right => Direction::Left,
+			Direction::Down => Direction::Up,
+			Direction::Left => Direction::Right,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn turns_directions() {
+		for direction in Direction::ALL {
+			assert_eq!(direction.turn_right().turn_left(), direction);
+			assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+			assert_eq!(direction.turn_left().turn_left(), direction.reverse());
+			assert_eq!(
+				direction.turn_right().step(),
+				direction.step().rotate_right()
+			);
+			assert_eq!(direction.turn_left().step(), direction.step().rotate_left());
+			assert_eq!(direction.reverse().step(), -direction.step());
+		}
+		assert_eq!(Direction::Up.turn_right(), Direction::Right);
+		assert_eq!(Direction::Up.turn_left(), Direction::Left);
+	}
+
+	#[test]
+	fn lists_steps_clockwise() {
+		let steps = Direction::ALL.map(Direction::step);
+		assert_eq!(steps, Point::ORTHOGONAL);
+		assert_eq!(
+			Point::NEIGHBOURS
+				.iter()
+				.step_by(2)
+				.copied()
+				.collect::<Vec<_>>(),
+			steps
+		);
+		assert_eq!(
+			Point::NEIGHBOURS
+				.iter()
+				.skip(1)
+				.step_by(2)
+				.copied()
+				.collect::<Vec<_>>(),
+			Point::DIAGONAL
+		);
+	}
+
+	#[test]
+	fn rotates_points_around_the_origin() {
+		let point = Point::new(3, -2);
+		assert_eq!(point.rotate_right(), Point::new(2, 3));
+		assert_eq!(point.rotate_left(), Point::new(-2, -3));
+		assert_eq!(point.rotate_right().rotate_right(), -point);
+		assert_eq!(point.rotate_right().rotate_left(), point);
+		assert_eq!(point.rotate_right().manhattan(), 5);
+	}
+
+	#[test]
+	fn converts_to_positions() {
+		assert_eq!(Point::new(2, 5).to_position(), Some((2, 5)));
+		assert_eq!(Point::new(-1, 5).to_position(), None);
+		assert_eq!(Point::new(2, -1).to_position(), None);
+		assert_eq!(Point::from((4, 7)), Point::new(4, 7));
+	}
+
+	#[test]
+	fn does_arithmetic() {
+		let mut point = Point::new(1, 2);
+		point += Point::new(3, -4);
+		assert_eq!(point, Point::new(4, -2));
+		point -= Point::new(1, 1);
+		assert_eq!(point, Point::new(3, -3));
+		assert_eq!(point * -2, Point::new(-6, 6));
+		assert_eq!(point.to_string(), "(3, -3)");
+	}
+}
@@ -0,0 +1,284 @@
+use std::{
+	fmt,
+	ops::{Index, IndexMut},
+};
+
+use crate::{error::ParseError, geometry::Point};
+
+/// A position in a grid as (x, y), with x growing to the right and y growing downwards
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+	/// Create a grid from its cells, given row by row
+	///
+	/// Panics if the number of cells does not match the size.
+	pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+		assert_eq!(
+			cells.len(),
+			width * height,
+			"cells do not match the grid size"
+		);
+		Grid {
+			width,
+			height,
+			cells,
+		}
+	}
+
+	/// Parse a grid with one row per line, converting each character with `cell`
+	///
+	/// `cell` returns a description of the expected characters for characters it rejects.
+	/// Every row has to be as wide as the first one. Trailing empty lines are ignored.
+	pub fn parse(
+		input: &str,
+		mut cell: impl FnMut(char) -> Result<T, &'static str>,
+	) -> Result<Self, ParseError> {
+		let mut width = None;
+		let mut height = 0;
+		let mut cells = Vec::new();
+
+		for line in input.trim_end_matches(['\n', '\r']).lines() {
+			let mut columns = 0;
+			for (index, c) in line.char_indices() {
+				if width.is_some_and(|width| columns == width) {
+					let token = &line[index..index + c.len_utf8()];
+					return Err(ParseError::at_token(input, token, "end of line"));
+				}
+				match cell(c) {
+					Ok(value) => cells.push(value),
+					Err(expected) => {
+						let token = &line[index..index + c.len_utf8()];
+						return Err(ParseError::at_token(input, token, expected));
+					}
+				}
+				columns += 1;
+			}
+
+			let width = *width.get_or_insert(columns);
+			if columns < width {
+				return Err(ParseError::at_token(
+					input,
+					&line[line.len()..],
+					format!("a row of {} cells", width),
+				));
+			}
+			height += 1;
+		}
+
+		Ok(Grid::new(width.unwrap_or(0), height, cells))
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	/// Whether the position lies within the grid
+	pub fn contains(&self, (x, y): Position) -> bool {
+		x < self.width && y < self.height
+	}
+
+	/// The cell at a position, or `None` outside of the grid
+	pub fn get(&self, position: Position) -> Option<&T> {
+		self.index_of(position).map(|index| &self.cells[index])
+	}
+
+	/// The mutable cell at a position, or `None` outside of the grid
+	pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+		self.index_of(position).map(|index| &mut self.cells[index])
+	}
+
+	/// The point as a position, or `None` if it lies outside of the grid
+	pub fn to_position(&self, point: Point) -> Option<Position> {
+		point
+			.to_position()
+			.filter(|position| self.contains(*position))
+	}
+
+	/// The position `step` away from `position`, or `None` if it lies outside of the grid
+	pub fn offset(&self, position: Position, step: Point) -> Option<Position> {
+		self.to_position(Point::from(position) + step)
+	}
+
+	/// All positions, row by row
+	pub fn positions(&self) -> impl Iterator<Item = Position> {
+		let width = self.width;
+		(0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+	}
+
+	/// All positions with their cells, row by row
+	pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+		self.positions().zip(self.cells.iter())
+	}
+
+	/// The position of the first cell matching the predicate, row by row
+	pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+		self.iter()
+			.find(|(_, cell)| predicate(cell))
+			.map(|(position, _)| position)
+	}
+
+	/// The orthogonal neighbours of a position within the grid
+	pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+		Point::ORTHOGONAL
+			.into_iter()
+			.filter_map(move |step| self.offset(position, step))
+	}
+
+	/// The orthogonal and diagonal neighbours of a position within the grid
+	pub fn all_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+		Point::NEIGHBOURS
+			.into_iter()
+			.filter_map(move |step| self.offset(position, step))
+	}
+
+	/// The cells of a row
+	///
+	/// Panics if the row is outside of the grid.
+	pub fn row(&self, y: usize) -> &[T] {
+		&self.cells[y * self.width..(y + 1) * self.width]
+	}
+
+	/// The cells of a column, from top to bottom
+	///
+	/// Panics if the column is outside of the grid.
+	pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+		assert!(x < self.width, "column outside of the grid");
+		self.cells.iter().skip(x).step_by(self.width)
+	}
+
+	/// The cells from `start` (inclusive) in steps of `step` until the edge of the grid
+	///
+	/// With diagonal steps this gives the diagonal views of the grid.
+	pub fn line(&self, start: Position, step: Point) -> impl Iterator<Item = &T> {
+		let first = self.contains(start).then_some(start);
+		std::iter::successors(first, move |position| self.offset(*position, step))
+			.map(|position| &self[position])
+	}
+
+	/// Convert every cell
+	pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+		Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+	}
+
+	/// Index of a position into the cells
+	fn index_of(&self, position: Position) -> Option<usize> {
+		self.contains(position)
+			.then(|| position.1 * self.width + position.0)
+	}
+}
+
+impl<T> Index<Position> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, position: Position) -> &T {
+		self.get(position).expect("position outside of the grid")
+	}
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+	fn index_mut(&mut self, position: Position) -> &mut T {
+		self.get_mut(position)
+			.expect("position outside of the grid")
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+	/// One line per row, with the cells of a row written next to each other
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for y in 0..self.height {
+			for cell in self.row(y) {
+				write!(f, "{}", cell)?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn digits(input: &str) -> Grid<u32> {
+		Grid::parse(input, |c| c.to_digit(10).ok_or("a digit")).unwrap()
+	}
+
+	fn sorted(positions: impl Iterator<Item = Position>) -> Vec<Position> {
+		let mut positions = positions.collect::<Vec<_>>();
+		positions.sort();
+		positions
+	}
+
+	#[test]
+	fn parses_rows_into_cells() {
+		let grid = digits("123\n456\n\n");
+		assert_eq!((grid.width(), grid.height()), (3, 2));
+		assert_eq!(grid.row(1), [4, 5, 6]);
+		assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+		assert_eq!(grid.to_string(), "123\n456\n");
+	}
+
+	#[test]
+	fn rejects_rows_of_other_widths() {
+		let parse = |input| Grid::parse(input, |c| c.to_digit(10).ok_or("a digit"));
+
+		let error = parse("123\n4567\n").unwrap_err();
+		assert_eq!((error.line, error.column), (2, 4));
+		let error = parse("123\n45\n").unwrap_err();
+		assert_eq!((error.line, error.column), (2, 3));
+		let error = parse("123\n4x6\n").unwrap_err();
+		assert_eq!(
+			(error.line, error.column, error.expected.as_str()),
+			(2, 2, "a digit")
+		);
+	}
+
+	#[test]
+	fn checks_bounds() {
+		let grid = digits("12\n34\n56\n");
+		assert!(grid.contains((1, 2)));
+		assert!(!grid.contains((2, 0)));
+		assert!(!grid.contains((0, 3)));
+		assert_eq!(grid.get((1, 2)), Some(&6));
+		assert_eq!(grid.get((2, 0)), None);
+		assert_eq!(grid.to_position(Point::new(-1, 0)), None);
+		assert_eq!(grid.offset((0, 0), Point::new(0, -1)), None);
+		assert_eq!(grid.offset((0, 0), Point::new(1, 2)), Some((1, 2)));
+	}
+
+	#[test]
+	fn finds_neighbours_within_the_grid() {
+		let grid = digits("123\n456\n789\n");
+		assert_eq!(sorted(grid.neighbours((0, 0))), [(0, 1), (1, 0)]);
+		assert_eq!(
+			sorted(grid.neighbours((1, 1))),
+			[(0, 1), (1, 0), (1, 2), (2, 1)]
+		);
+		assert_eq!(
+			sorted(grid.all_neighbours((2, 0))),
+			[(1, 0), (1, 1), (2, 1)]
+		);
+		assert_eq!(grid.all_neighbours((1, 1)).count(), 8);
+	}
+
+	#[test]
+	fn walks_lines_until_the_edge() {
+		let grid = digits("123\n456\n789\n");
+		let line = |start, step| grid.line(start, step).copied().collect::<Vec<_>>();
+		assert_eq!(line((0, 0), Point::new(1, 1)), [1, 5, 9]);
+		assert_eq!(line((2, 0), Point::new(-1, 1)), [3, 5, 7]);
+		assert_eq!(line((3, 0), Point::new(-1, 0)), []);
+	}
+}
@@ -0,0 +1,201 @@
+use std::{
+	borrow::Cow,
+	env, fs,
+	io::{self, Read},
+	path::PathBuf,
+};
+
+use crate::error::ParseError;
+
+/// Environment variable overriding the default input directory
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read a day's puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+	/// Look up the day's input below a directory, see [`InputSource::path`]
+	Directory(PathBuf),
+	/// Read the input from an explicit file
+	File(PathBuf),
+	/// Read the input from the standard input
+	Stdin,
+}
+
+impl Default for InputSource {
+	/// The directory from `AOC_INPUT_DIR`, falling back to the inputs checked into this repository
+	fn default() -> Self {
+		match env::var_os(INPUT_DIR_VAR) {
+			Some(dir) => InputSource::Directory(PathBuf::from(dir)),
+			None => {
+				InputSource::Directory([env!("CARGO_MANIFEST_DIR"), "src", "days"].iter().collect())
+			}
+		}
+	}
+}
+
+impl InputSource {
+	/// The file a day's input is read from, if it comes from a file
+	///
+	/// Within a directory, `dayNN/input` (the layout of this repository) is preferred over `dayNN.txt`.
+	pub fn path(&self, day: u8) -> Option<PathBuf> {
+		match self {
+			InputSource::Directory(dir) => {
+				let nested = dir.join(format!("day{:02}", day)).join("input");
+				let flat = dir.join(format!("day{:02}.txt", day));
+				Some(if !nested.exists() && flat.exists() {
+					flat
+				} else {
+					nested
+				})
+			}
+			InputSource::File(path) => Some(path.clone()),
+			InputSource::Stdin => None,
+		}
+	}
+
+	/// Human readable name of a day's input: its path, or `stdin`
+	pub fn name(&self, day: u8) -> String {
+		match self.path(day) {
+			Some(path) => path.display().to_string(),
+			None => "stdin".to_string(),
+		}
+	}
+
+	/// Read the puzzle input of a day
+	pub fn read(&self, day: u8) -> io::Result<String> {
+		match self.path(day) {
+			Some(path) => fs::read_to_string(&path).map_err(|error| {
+				io::Error::new(
+					error.kind(),
+					format!("unable to read `{}`: {}", path.display(), error),
+				)
+			}),
+			None => {
+				let mut content = String::new();
+				io::stdin().read_to_string(&mut content)?;
+				Ok(content)
+			}
+		}
+	}
+}
+
+/// A puzzle input with consistent line endings and whitespace, see [`normalise`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalised<'a> {
+	pub content: Cow<'a, str>,
+	/// The number of leading blank lines that were removed
+	pub line_offset: usize,
+}
+
+impl Normalised<'_> {
+	/// Locate an error in the normalised content in the original input instead
+	///
+	/// Only whole lines and the ends of lines are removed, so columns stay the same.
+	pub fn locate(&self, mut error: ParseError) -> ParseError {
+		error.line += self.line_offset;
+		error
+	}
+}
+
+/// Normalise the line endings and whitespace of a puzzle input
+///
+/// `\r\n` becomes `\n`, trailing whitespace is removed from every line and leading and trailing
+/// blank lines are removed, so inputs saved on Windows or by editors parse the same. Blank lines
+/// between other lines are kept, as some puzzles separate sections with them. A non-empty result
+/// ends with a single `\n`.
+pub fn normalise(content: &str) -> Normalised<'_> {
+	let mut lines = content
+		.split('\n')
+		.map(|line| line.trim_end_matches([' ', '\t', '\r']))
+		.collect::<Vec<_>>();
+
+	let line_offset = lines.iter().take_while(|line| line.is_empty()).count();
+	while lines.last().is_some_and(|line| line.is_empty()) {
+		lines.pop();
+	}
+
+	let mut normalised = String::with_capacity(content.len());
+	for line in lines.iter().skip(line_offset) {
+		normalised.push_str(line);
+		normalised.push('\n');
+	}
+
+	Normalised {
+		content: if normalised == content {
+			Cow::Borrowed(content)
+		} else {
+			Cow::Owned(normalised)
+		},
+		line_offset,
+	}
+}
+
+/// Identify an input by its content, so stored answers follow the input rather than its location
+///
+/// This is the 64 bit FNV-1a hash of the normalised input, in hexadecimal, so line endings and
+/// trailing whitespace do not matter.
+pub fn fingerprint(content: &str) -> String {
+	let mut hash: u64 = 0xcbf29ce484222325;
+	for byte in normalise(content).content.bytes() {
+		hash ^= byte as u64;
+		hash = hash.wrapping_mul(0x100000001b3);
+	}
+	format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keeps_normalised_input_borrowed() {
+		let normalised = normalise("1 2\n\n3 4\n");
+		assert!(matches!(normalised.content, Cow::Borrowed("1 2\n\n3 4\n")));
+		assert_eq!(normalised.line_offset, 0);
+	}
+
+	#[test]
+	fn converts_crlf_line_endings() {
+		assert_eq!(normalise("1 2\r\n3 4\r\n").content, "1 2\n3 4\n");
+		assert_eq!(normalise("1 2\r\n3 4").content, "1 2\n3 4\n");
+	}
+
+	#[test]
+	fn removes_trailing_whitespace() {
+		assert_eq!(normalise("1 2 \t\n3 4  \r\n").content, "1 2\n3 4\n");
+		assert_eq!(normalise("  1 2\n").content, "  1 2\n");
+	}
+
+	#[test]
+	fn removes_leading_and_trailing_blank_lines() {
+		let normalised = normalise("\n \r\n\t\n1 2\n\n3 4\n\n \n");
+		assert_eq!(normalised.content, "1 2\n\n3 4\n");
+		assert_eq!(normalised.line_offset, 3);
+	}
+
+	#[test]
+	fn normalises_blank_input_to_nothing() {
+		for content in ["", "\n", " \r\n\n\t"] {
+			assert_eq!(normalise(content).content, "");
+		}
+	}
+
+	#[test]
+	fn locates_errors_in_the_raw_input() {
+		let raw = "\r\n  \r\n12 34  \r\n56 x7\r\n\r\n";
+		let normalised = normalise(raw);
+		let content = &normalised.content;
+		let token = &content[content.find('x').unwrap()..][..1];
+
+		let error = normalised.locate(ParseError::at_token(content, token, "a digit"));
+		assert_eq!((error.line, error.column), (4, 4));
+		assert_eq!(
+			raw.lines()
+				.nth(error.line - 1)
+				.unwrap()
+				.chars()
+				.nth(error.column - 1),
+			Some('x')
+		);
+	}
+}
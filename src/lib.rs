@@ -0,0 +1,29 @@
+//! Advent of Code 2024 solutions
+//!
+//! Every day implements [`Solution`] and is registered in [`days::DAYS`]. Each day module also
+//! exposes plain functions that take the puzzle input as text and return the typed answer,
+//! e.g. [`days::day07::solve`].
+
+pub mod bench;
+pub mod cancel;
+pub mod days;
+pub mod differential;
+pub mod error;
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod params;
+pub mod pool;
+pub mod rng;
+pub mod runner;
+pub mod scan;
+pub mod solution;
+pub mod timing;
+pub mod trace;
+pub mod verify;
+
+pub use days::{Day, Part};
+pub use error::{Error, ParseError};
+pub use solution::Solution;
@@ -1,5 +1,329 @@
-mod days;
+mod cli;
+mod report;
+mod scaffold;
+mod watch;
 
-fn main() {
-	days::day09::main();
+use std::{
+	fs,
+	io::{self, Write},
+	panic,
+	process::ExitCode,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use advent_of_code_2024::{
+	bench::{self, Baseline},
+	days, differential, fuzz,
+	params::Overrides,
+	rng::Rng,
+	runner::{self, PartResult},
+	timing, trace,
+	verify::{self, Answers, Outcome},
+	Day, Error,
+};
+use cli::Command;
+
+fn main() -> ExitCode {
+	match run() {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			eprintln!("error: {}", error);
+			if let Error::Usage(_) = error {
+				eprintln!("\n{}", cli::USAGE);
+			}
+			error.exit_code()
+		}
+	}
+}
+
+/// Execute the command given on the command line
+fn run() -> Result<(), Error> {
+	match cli::parse(std::env::args().skip(1))? {
+		Command::Help => println!("{}", cli::USAGE),
+		Command::Run {
+			days,
+			part,
+			input,
+			timing,
+			format,
+			jobs,
+			batch,
+			params,
+			example,
+			timeouts,
+			trace,
+		} => {
+			let selected = select(&days)?;
+			check_params(&selected, &params)?;
+			trace::set_level(trace);
+
+			if example {
+				let checks = selected
+					.iter()
+					.flat_map(|day| verify::check_examples(day, part))
+					.collect::<Vec<_>>();
+
+				report::print_examples(&checks, format);
+				let failed = checks
+					.iter()
+					.filter(|check| check.outcome != Outcome::Pass)
+					.count();
+				if failed > 0 {
+					return Err(Error::Verification { failed });
+				}
+				return Ok(());
+			}
+
+			if let Some(dir) = batch {
+				let inputs = runner::batch_inputs(&dir)?;
+				let results =
+					runner::run_batch(selected[0], &inputs, part, &params, &timeouts, jobs);
+
+				report::print_batch(&inputs, &results, part, format);
+				let failed = results
+					.iter()
+					.filter(|result| match result {
+						Ok(part_results) => part_results.iter().any(PartResult::timed_out),
+						Err(_) => true,
+					})
+					.count();
+				if failed > 0 {
+					return Err(Error::BatchFailed { inputs: failed });
+				}
+				return Ok(());
+			}
+
+			if let Some(timing) = timing {
+				// time one day after the other, so the days do not compete for cores
+				let timings = selected
+					.iter()
+					.map(|day| timing::time_day(day, &input, part, &params, &timing))
+					.collect::<Result<Vec<_>, _>>()?;
+
+				report::print_timings(&timings, format);
+				return Ok(());
+			}
+
+			let mut results = Vec::new();
+			let mut failed = 0;
+			for result in runner::run_days(&selected, &input, part, &params, &timeouts, jobs) {
+				match result {
+					Ok(day_results) => {
+						// timed out parts are reported in the table
+						if day_results.iter().any(PartResult::timed_out) {
+							failed += 1;
+						}
+						results.extend(day_results);
+					}
+					Err(error) => {
+						eprintln!("error: {}", error);
+						failed += 1;
+					}
+				}
+			}
+
+			report::print_results(&results, format);
+			if failed > 0 {
+				return Err(Error::Failed { days: failed });
+			}
+		}
+		Command::Generate {
+			day,
+			seed,
+			size,
+			output,
+		} => {
+			let day = days::get(day)?;
+			let input_size = day.solution.input_size();
+			let size = size.unwrap_or(input_size.default);
+			let seed = seed.unwrap_or_else(time_seed);
+
+			let content = day.solution.generate(&mut Rng::new(seed), size);
+			match output {
+				Some(path) => fs::write(path, content)?,
+				None => io::stdout().lock().write_all(content.as_bytes())?,
+			}
+			eprintln!(
+				"generated day {} with --seed {} --size {} ({})",
+				day.number, seed, size, input_size.unit
+			);
+		}
+		Command::Diff {
+			days,
+			part,
+			seeds,
+			params,
+		} => {
+			let selected = select(&days)?;
+			check_params(&selected, &params)?;
+
+			// panics are reported as disagreements, and shrinking may trigger many of them
+			panic::set_hook(Box::new(|_| {}));
+			let mut failed = 0;
+			for day in selected {
+				let check = differential::check_day(day, part, seeds, &params)?;
+				report::print_day_check(&check);
+				if check.disagreement.is_some() {
+					failed += 1;
+				}
+			}
+
+			if failed > 0 {
+				return Err(Error::Disagreed { days: failed });
+			}
+		}
+		Command::Fuzz { days, fuzzing } => {
+			let selected = select(&days)?;
+
+			// panics are reported with the input that caused them
+			panic::set_hook(Box::new(|_| {}));
+			let mut failed = 0;
+			for day in selected {
+				match fuzz::fuzz_day(day, &fuzzing) {
+					Ok(handled) => println!("day {}  {} inputs handled", day.number, handled),
+					Err(crash) => {
+						report::print_crash(&crash);
+						failed += 1;
+					}
+				}
+			}
+
+			if failed > 0 {
+				return Err(Error::Crashed { days: failed });
+			}
+		}
+		Command::Bench {
+			days,
+			input,
+			timing,
+			format,
+			threshold,
+			baseline: path,
+			record,
+		} => {
+			let selected = select(&days)?;
+			let mut baseline = Baseline::load(&path)?;
+
+			// the baseline holds the medians of the default parameters
+			let mut comparisons = Vec::new();
+			let mut timings = Vec::new();
+			for day in selected {
+				let day_timings =
+					timing::time_day(day, &input, None, &Overrides::default(), &timing)?;
+				comparisons.extend(bench::compare(&day_timings, &baseline, threshold));
+				timings.push(day_timings);
+			}
+			report::print_comparisons(&comparisons, threshold, format);
+
+			if record {
+				for day_timings in &timings {
+					baseline.record(day_timings);
+				}
+				baseline.save(&path)?;
+				println!(
+					"recorded {} stages in {}",
+					comparisons.len(),
+					path.display()
+				);
+				return Ok(());
+			}
+
+			let regressed = comparisons
+				.iter()
+				.filter(|comparison| comparison.regressed)
+				.count();
+			if regressed > 0 {
+				return Err(Error::Regressed { stages: regressed });
+			}
+		}
+		Command::Params { days } => {
+			let selected = select(&days)?;
+			report::print_params(&selected);
+		}
+		Command::NewDay { day, root } => {
+			for path in scaffold::new_day(&root, day)? {
+				println!("wrote {}", path.display());
+			}
+		}
+		Command::Watch(settings) => watch::watch(&settings)?,
+		Command::Verify {
+			days,
+			input,
+			answers: path,
+			record,
+		} => {
+			let selected = select(&days)?;
+			let mut answers = Answers::load(&path)?;
+
+			let checks = selected
+				.iter()
+				.flat_map(|day| verify::verify_day(day, &input, &answers))
+				.collect::<Vec<_>>();
+
+			for check in &checks {
+				println!("day {} part {}  {}", check.day, check.part, check.outcome);
+			}
+
+			let count = |matches: fn(&Outcome) -> bool| {
+				checks
+					.iter()
+					.filter(|check| matches(&check.outcome))
+					.count()
+			};
+			let passed = count(|outcome| matches!(outcome, Outcome::Pass));
+			let missing = count(|outcome| matches!(outcome, Outcome::Missing { .. }));
+			let failed = checks.len() - passed - missing;
+			println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+			if record && missing > 0 {
+				for check in checks {
+					if let (Outcome::Missing { actual }, Some(fingerprint)) =
+						(check.outcome, check.fingerprint)
+					{
+						answers.insert(check.day, check.part, &fingerprint, actual);
+					}
+				}
+				answers.save(&path)?;
+				println!("recorded {} answers in {}", missing, path.display());
+			}
+
+			if failed > 0 {
+				return Err(Error::Verification { failed });
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Look up the selected days, all up front so a typo does not run half of the selection
+fn select(days: &[u8]) -> Result<Vec<&'static Day>, Error> {
+	days.iter().map(|number| days::get(*number)).collect()
+}
+
+/// Reject overrides of parameters none of the selected days declares, which are likely typos,
+/// and invalid parameter values before solving anything
+fn check_params(selected: &[&Day], params: &Overrides) -> Result<(), Error> {
+	for name in params.names() {
+		let declared = selected
+			.iter()
+			.any(|day| day.solution.params().iter().any(|param| param.name == name));
+		if !declared {
+			return Err(Error::Usage(format!(
+				"unknown parameter `{}` for the selected days, see `aoc params`",
+				name
+			)));
+		}
+	}
+	for day in selected {
+		day.configure(params)?;
+	}
+	Ok(())
+}
+
+/// A seed for generated inputs that differs between invocations
+fn time_seed() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |elapsed| elapsed.as_nanos() as u64)
 }
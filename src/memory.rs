@@ -0,0 +1,171 @@
+//! Allocation accounting of the timed stages
+//!
+//! With the `count-allocations` feature, [`CountingAllocator`] is the global allocator and
+//! [`measure`] reports the allocations of a stage. Without it, [`measure`] reports nothing and
+//! allocations cost what they always do. Counting adds a few atomic operations to every
+//! allocation, so timings with the feature are slightly higher.
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Allocations made while a stage ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocations {
+	/// The number of allocations, including reallocations
+	pub count: usize,
+	/// The bytes requested by all allocations together
+	pub bytes: usize,
+	/// The most bytes the stage had allocated at once
+	pub peak: usize,
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation
+///
+/// Installed as the global allocator by the `count-allocations` feature.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+	fn allocated(&self, size: usize) {
+		COUNT.fetch_add(1, Ordering::Relaxed);
+		BYTES.fetch_add(size, Ordering::Relaxed);
+		let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+		PEAK.fetch_max(live, Ordering::Relaxed);
+	}
+
+	fn freed(&self, size: usize) {
+		LIVE.fetch_sub(size, Ordering::Relaxed);
+	}
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let pointer = System.alloc(layout);
+		if !pointer.is_null() {
+			self.allocated(layout.size());
+		}
+		pointer
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		let pointer = System.alloc_zeroed(layout);
+		if !pointer.is_null() {
+			self.allocated(layout.size());
+		}
+		pointer
+	}
+
+	unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+		System.dealloc(pointer, layout);
+		self.freed(layout.size());
+	}
+
+	unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		let new_pointer = System.realloc(pointer, layout, new_size);
+		if !new_pointer.is_null() {
+			// counted as a new allocation that replaces the old one, freed first so that growing
+			// in place does not count both sizes towards the peak
+			self.freed(layout.size());
+			self.allocated(new_size);
+		}
+		new_pointer
+	}
+}
+
+/// Whether allocations are counted, i.e. the `count-allocations` feature is enabled
+pub fn counting() -> bool {
+	cfg!(feature = "count-allocations")
+}
+
+/// Run `stage` once and report its allocations, or return `None` without running it if
+/// allocations are not counted
+///
+/// The counters are global, so allocations of other threads running at the same time are
+/// included. The peak includes the output of the stage, which is dropped afterwards.
+pub fn measure<T>(stage: impl FnOnce() -> T) -> Option<Allocations> {
+	if !counting() {
+		return None;
+	}
+
+	let count = COUNT.load(Ordering::Relaxed);
+	let bytes = BYTES.load(Ordering::Relaxed);
+	let live = LIVE.load(Ordering::Relaxed);
+	PEAK.store(live, Ordering::Relaxed);
+
+	let output = stage();
+
+	let allocations = Allocations {
+		count: COUNT.load(Ordering::Relaxed) - count,
+		bytes: BYTES.load(Ordering::Relaxed) - bytes,
+		peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+	};
+	drop(output);
+	Some(allocations)
+}
+
+/// Format a number of bytes with three significant digits and a fitting binary unit
+pub fn format_bytes(bytes: usize) -> String {
+	if bytes < 1024 {
+		return format!("{} B", bytes);
+	}
+
+	let mut value = bytes as f64 / 1024.0;
+	let mut unit = "KiB";
+	for larger in ["MiB", "GiB"] {
+		if value < 1024.0 {
+			break;
+		}
+		value /= 1024.0;
+		unit = larger;
+	}
+
+	let precision = if value < 10.0 {
+		2
+	} else if value < 100.0 {
+		1
+	} else {
+		0
+	};
+	format!("{:.*} {}", precision, value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	#[cfg(feature = "count-allocations")]
+	fn measures_allocations() {
+		let allocations = measure(|| vec![0u8; 1 << 20]).unwrap();
+		assert!(allocations.count >= 1);
+		assert!(allocations.bytes >= 1 << 20);
+		assert!(allocations.peak >= 1 << 20);
+	}
+
+	#[test]
+	#[cfg(not(feature = "count-allocations"))]
+	fn measures_nothing_without_counting() {
+		assert_eq!(measure(|| vec![0u8; 1 << 20]), None);
+	}
+
+	#[test]
+	fn formats_bytes() {
+		assert_eq!(format_bytes(0), "0 B");
+		assert_eq!(format_bytes(1023), "1023 B");
+		assert_eq!(format_bytes(1024), "1.00 KiB");
+		assert_eq!(format_bytes(1536), "1.50 KiB");
+		assert_eq!(format_bytes(100 << 10), "100 KiB");
+		assert_eq!(format_bytes(15 << 20), "15.0 MiB");
+		assert_eq!(format_bytes(3 << 30), "3.00 GiB");
+	}
+}
@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A named, tunable parameter of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+	pub name: &'static str,
+	/// The value used unless it is overridden
+	pub default: &'static str,
+	pub description: &'static str,
+}
+
+/// Parameter values given on the command line, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides {
+	values: BTreeMap<String, String>,
+}
+
+impl Overrides {
+	/// Parse a `name=value` assignment and add it, replacing an earlier value
+	pub fn add(&mut self, assignment: &str) -> Result<(), String> {
+		let (name, value) = assignment
+			.split_once('=')
+			.filter(|(name, _)| !name.is_empty())
+			.ok_or_else(|| format!("invalid parameter `{}`, expected name=value", assignment))?;
+		self.values.insert(name.to_string(), value.to_string());
+		Ok(())
+	}
+
+	/// The names of all overridden parameters
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.values.keys().map(String::as_str)
+	}
+
+	/// The names and values of all overridden parameters
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.values
+			.iter()
+			.map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+}
+
+/// The values of the declared parameters of a solution, overridden where given
+pub struct Values<'a> {
+	declared: &'static [Param],
+	overrides: &'a Overrides,
+}
+
+impl<'a> Values<'a> {
+	pub fn new(declared: &'static [Param], overrides: &'a Overrides) -> Self {
+		Values {
+			declared,
+			overrides,
+		}
+	}
+
+	/// The raw value of a parameter
+	///
+	/// Panics if the parameter is not declared.
+	pub fn raw(&self, name: &str) -> &str {
+		let param = self
+			.declared
+			.iter()
+			.find(|param| param.name == name)
+			.unwrap_or_else(|| panic!("parameter `{}` is not declared", name));
+
+		self.overrides
+			.values
+			.get(name)
+			.map_or(param.default, String::as_str)
+	}
+
+	/// The value of a parameter, converted to `T`
+	pub fn get<T: FromStr>(&self, name: &str) -> Result<T, String> {
+		let value = self.raw(name);
+		value
+			.parse()
+			.map_err(|_| format!("invalid value `{}` for parameter `{}`", value, name))
+	}
+
+	/// The comma separated values of a parameter, each converted to `T`
+	pub fn list<T: FromStr>(&self, name: &str) -> Result<Vec<T>, String> {
+		let value = self.raw(name);
+		value
+			.split(',')
+			.map(|item| {
+				item.trim()
+					.parse()
+					.map_err(|_| format!("invalid value `{}` in parameter `{}`", item, name))
+			})
+			.collect()
+	}
+}
+
+/// Typed parameters of a solution, built from their named values
+pub trait Parameters: Sized + 'static {
+	/// The parameters with their defaults
+	const DECLARED: &'static [Param];
+
+	/// Convert the values of the declared parameters
+	fn from_values(values: &Values) -> Result<Self, String>;
+
+	/// The parameters with all defaults
+	fn defaults() -> Self {
+		Self::from_values(&Values::new(Self::DECLARED, &Overrides::default()))
+			.expect("the defaults of the parameters are valid")
+	}
+}
+
+/// No parameters
+impl Parameters for () {
+	const DECLARED: &'static [Param] = &[];
+
+	fn from_values(_values: &Values) -> Result<Self, String> {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const DECLARED: &[Param] = &[
+		Param {
+			name: "steps",
+			default: "25",
+			description: "how often to blink",
+		},
+		Param {
+			name: "size",
+			default: "7,7",
+			description: "width and height of the area",
+		},
+	];
+
+	fn overrides(assignments: &[&str]) -> Overrides {
+		let mut overrides = Overrides::default();
+		for assignment in assignments {
+			overrides.add(assignment).unwrap();
+		}
+		overrides
+	}
+
+	#[test]
+	fn parses_assignments() {
+		let overrides = overrides(&["steps=10", "size=", "steps=75", "eq=a=b"]);
+		assert_eq!(
+			overrides.iter().collect::<Vec<_>>(),
+			[("eq", "a=b"), ("size", ""), ("steps", "75")]
+		);
+
+		let mut overrides = Overrides::default();
+		assert!(overrides.add("steps").is_err());
+		assert!(overrides.add("=10").is_err());
+		assert_eq!(overrides.names().count(), 0);
+	}
+
+	#[test]
+	fn falls_back_to_defaults() {
+		let none = Overrides::default();
+		let values = Values::new(DECLARED, &none);
+		assert_eq!(values.get::<u32>("steps"), Ok(25));
+		assert_eq!(values.list::<u32>("size"), Ok(vec![7, 7]));
+
+		let some = overrides(&["size=101, 103"]);
+		let values = Values::new(DECLARED, &some);
+		assert_eq!(values.get::<u32>("steps"), Ok(25));
+		assert_eq!(values.list::<u32>("size"), Ok(vec![101, 103]));
+	}
+
+	#[test]
+	fn rejects_invalid_values() {
+		let invalid = overrides(&["steps=-1", "size=3,x"]);
+		let values = Values::new(DECLARED, &invalid);
+		assert_eq!(
+			values.get::<u32>("steps"),
+			Err("invalid value `-1` for parameter `steps`".to_string())
+		);
+		assert_eq!(
+			values.list::<u32>("size"),
+			Err("invalid value `x` in parameter `size`".to_string())
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "parameter `seed` is not declared")]
+	fn panics_on_undeclared_parameters() {
+		let none = Overrides::default();
+		Values::new(DECLARED, &none).raw("seed");
+	}
+}
@@ -0,0 +1,72 @@
+use std::{
+	num::NonZeroUsize,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		mpsc,
+	},
+	thread,
+};
+
+/// The number of worker threads to use by default
+pub fn default_threads() -> usize {
+	thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Apply `f` to every item on up to `threads` worker threads
+///
+/// The results are returned in the order of the items, regardless of completion order.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+	T: Sync,
+	R: Send,
+	F: Fn(&T) -> R + Sync,
+{
+	let next = AtomicUsize::new(0);
+	let (sender, receiver) = mpsc::channel();
+
+	thread::scope(|scope| {
+		for _ in 0..threads.clamp(1, items.len().max(1)) {
+			let sender = sender.clone();
+			let (next, f) = (&next, &f);
+
+			scope.spawn(move || loop {
+				// workers claim the next unclaimed item until all are taken
+				let index = next.fetch_add(1, Ordering::Relaxed);
+				let Some(item) = items.get(index) else {
+					break;
+				};
+				sender
+					.send((index, f(item)))
+					.expect("the receiver outlives the workers");
+			});
+		}
+	});
+	drop(sender);
+
+	let mut results = receiver.into_iter().collect::<Vec<_>>();
+	results.sort_by_key(|(index, _)| *index);
+	results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+	#[test]
+	fn returns_results_in_the_order_of_the_items() {
+		// the first items take the longest, so they complete last
+		let items = (0..8).rev().collect::<Vec<u64>>();
+		let results = map(&items, 4, |millis| {
+			thread::sleep(Duration::from_millis(millis * 5));
+			millis * 10
+		});
+		assert_eq!(results, [70, 60, 50, 40, 30, 20, 10, 0]);
+	}
+
+	#[test]
+	fn maps_without_items() {
+		assert_eq!(map(&[] as &[u8], 4, |item| *item), []);
+	}
+}
@@ -0,0 +1,506 @@
+use std::path::PathBuf;
+
+use advent_of_code_2024::{
+	bench::Comparison,
+	differential::DayCheck,
+	fuzz::Crash,
+	memory,
+	runner::PartResult,
+	timing::{self, DayTimings, Stage},
+	verify::{ExampleCheck, Outcome},
+	Day, Error, Part,
+};
+
+/// Output format of results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+	/// Tables for humans
+	#[default]
+	Text,
+	/// A JSON array with one record per part or stage
+	Json,
+}
+
+/// Print the answers of a run, one row or record per part
+pub fn print_results(results: &[PartResult], format: Format) {
+	match format {
+		Format::Text => print_table(results),
+		Format::Json => print_json(results.iter().map(part_record)),
+	}
+}
+
+/// Print the answers of a batch run, one row per input file or one record per part
+///
+/// Failed inputs get a row or record with their error instead of the answers.
+pub fn print_batch(
+	inputs: &[PathBuf],
+	results: &[Result<Vec<PartResult>, Error>],
+	part: Option<Part>,
+	format: Format,
+) {
+	match format {
+		Format::Text => print_batch_table(inputs, results, part),
+		Format::Json => print_json(inputs.iter().zip(results).flat_map(|(input, result)| {
+			let records: Vec<JsonObject> = match result {
+				Ok(part_results) => part_results.iter().map(part_record).collect(),
+				Err(error) => vec![JsonObject::new()
+					.string("input", &input.display().to_string())
+					.string("error", &error.to_string())],
+			};
+			records
+		})),
+	}
+}
+
+/// The JSON record of the answer to a part, with a null answer if it timed out
+fn part_record(result: &PartResult) -> JsonObject {
+	JsonObject::new()
+		.number("day", result.day)
+		.number("part", result.part)
+		.optional_string("answer", result.answer.solved())
+		.bool("timed_out", result.timed_out())
+		.number("duration_ns", result.elapsed.as_nanos())
+		.string("input", &result.input)
+		.string("fingerprint", &result.fingerprint)
+}
+
+/// Print the answers of a batch run as a table, one row per input file
+fn print_batch_table(
+	inputs: &[PathBuf],
+	results: &[Result<Vec<PartResult>, Error>],
+	part: Option<Part>,
+) {
+	let parts = Part::selected(part).collect::<Vec<_>>();
+	let names = inputs
+		.iter()
+		.map(|input| {
+			input.file_name().map_or_else(
+				|| input.display().to_string(),
+				|name| name.to_string_lossy().into_owned(),
+			)
+		})
+		.collect::<Vec<_>>();
+
+	let name_width = names
+		.iter()
+		.map(String::len)
+		.max()
+		.unwrap_or(0)
+		.max("input".len());
+	let answer_width = results
+		.iter()
+		.flatten()
+		.flatten()
+		.map(|result| result.answer.to_string().len())
+		.max()
+		.unwrap_or(0)
+		.max("part 1".len());
+
+	let mut header = format!("{:<width$}", "input", width = name_width);
+	for part in &parts {
+		header.push_str(&format!(
+			"  {:<width$}",
+			format!("part {}", part),
+			width = answer_width
+		));
+	}
+	println!("{}", header.trim_end());
+
+	for (name, result) in names.iter().zip(results) {
+		let mut row = format!("{:<width$}", name, width = name_width);
+		match result {
+			Ok(part_results) => {
+				for result in part_results {
+					row.push_str(&format!(
+						"  {:<width$}",
+						result.answer.to_string(),
+						width = answer_width
+					));
+				}
+			}
+			Err(error) => row.push_str(&format!("  error: {}", error)),
+		}
+		println!("{}", row.trim_end());
+	}
+}
+
+/// Print the outcome of each part of each example, one line or record per part
+pub fn print_examples(checks: &[ExampleCheck], format: Format) {
+	match format {
+		Format::Text => {
+			for check in checks {
+				println!(
+					"day {} example {} part {}  {}",
+					check.day, check.example, check.part, check.outcome
+				);
+			}
+			let passed = checks
+				.iter()
+				.filter(|check| check.outcome == Outcome::Pass)
+				.count();
+			println!("{} passed, {} failed", passed, checks.len() - passed);
+		}
+		Format::Json => print_json(checks.iter().map(|check| {
+			let record = JsonObject::new()
+				.number("day", check.day)
+				.number("example", check.example)
+				.number("part", check.part);
+			match &check.outcome {
+				Outcome::Pass => record.string("outcome", "pass"),
+				Outcome::Fail { expected, actual } => record
+					.string("outcome", "fail")
+					.string("expected", expected)
+					.string("answer", actual),
+				Outcome::Missing { actual } => {
+					record.string("outcome", "missing").string("answer", actual)
+				}
+				Outcome::Error(message) => {
+					record.string("outcome", "error").string("error", message)
+				}
+			}
+		})),
+	}
+}
+
+/// Print the result of checking a day against its reference solution
+///
+/// A disagreement is printed with its shrunk input and the arguments of `generate` that
+/// reproduce the original input.
+pub fn print_day_check(check: &DayCheck) {
+	let Some(disagreement) = &check.disagreement else {
+		println!("day {}  {} inputs agree", check.day, check.agreed);
+		return;
+	};
+
+	println!(
+		"day {}  part {} disagrees after {} inputs, generated with --seed {} --size {}",
+		check.day, disagreement.part, check.agreed, disagreement.seed, disagreement.size
+	);
+	println!("  smallest input:");
+	for line in disagreement.input.lines() {
+		println!("    {}", line);
+	}
+	println!("  answer:    {}", disagreement.answer);
+	println!("  reference: {}", disagreement.reference);
+}
+
+/// Print an input a day failed on while fuzzing, as an escaped string
+pub fn print_crash(crash: &Crash) {
+	println!(
+		"day {}  {} after {} inputs, on the input:",
+		crash.day, crash.failure, crash.handled
+	);
+	println!("  {:?}", String::from_utf8_lossy(&crash.input));
+}
+
+/// Print the parameters of each day with their defaults
+pub fn print_params(days: &[&Day]) {
+	for day in days {
+		println!("Day {}", day.number);
+		let params = day.solution.params();
+		if params.is_empty() {
+			println!("  no parameters");
+		}
+		for param in params {
+			println!("  {}={}", param.name, param.default);
+			println!("      {}", param.description);
+		}
+	}
+}
+
+/// Print the timings of each day, one table per day or one record per stage
+pub fn print_timings(days: &[DayTimings], format: Format) {
+	match format {
+		Format::Text => days.iter().for_each(print_timing_table),
+		Format::Json => print_json(days.iter().flat_map(|day| {
+			day.stages.iter().map(|timing| {
+				let (stage, part) = match timing.stage {
+					Stage::Parse => ("parse", None),
+					Stage::Part(part) => ("part", Some(part)),
+				};
+
+				JsonObject::new()
+					.number("day", day.day)
+					.string("stage", stage)
+					.optional_number("part", part)
+					.optional_string("answer", timing.answer.as_deref())
+					.number("min_ns", timing.stats.min.as_nanos())
+					.number("median_ns", timing.stats.median.as_nanos())
+					.number("mean_ns", timing.stats.mean.as_nanos())
+					.optional_number(
+						"allocations",
+						timing.allocations.map(|allocations| allocations.count),
+					)
+					.optional_number(
+						"allocated_bytes",
+						timing.allocations.map(|allocations| allocations.bytes),
+					)
+					.optional_number(
+						"peak_bytes",
+						timing.allocations.map(|allocations| allocations.peak),
+					)
+					.string("input", &day.input)
+					.string("fingerprint", &day.fingerprint)
+			})
+		})),
+	}
+}
+
+/// Print the benchmarked stages with their baselines, one row or record per stage
+pub fn print_comparisons(comparisons: &[Comparison], threshold: f64, format: Format) {
+	match format {
+		Format::Text => {
+			println!(
+				"{:>3}  {:<8} {:>10} {:>10} {:>8}",
+				"day", "stage", "baseline", "median", "change"
+			);
+			for comparison in comparisons {
+				let baseline = comparison
+					.baseline
+					.map_or("-".to_string(), timing::format_duration);
+				let change = comparison
+					.change
+					.map_or("-".to_string(), |change| format!("{:+.1}%", change));
+				let note = match comparison.change {
+					None => "  new",
+					Some(_) if comparison.regressed => "  regressed",
+					Some(_) => "",
+				};
+				println!(
+					"{:>3}  {:<8} {:>10} {:>10} {:>8}{}",
+					comparison.day,
+					comparison.stage.to_string(),
+					baseline,
+					timing::format_duration(comparison.median),
+					change,
+					note
+				);
+			}
+
+			let regressed = comparisons
+				.iter()
+				.filter(|comparison| comparison.regressed)
+				.count();
+			println!(
+				"{} regressed by more than {}%, {} without a baseline",
+				regressed,
+				threshold,
+				comparisons
+					.iter()
+					.filter(|comparison| comparison.baseline.is_none())
+					.count()
+			);
+		}
+		Format::Json => print_json(comparisons.iter().map(|comparison| {
+			let (stage, part) = match comparison.stage {
+				Stage::Parse => ("parse", None),
+				Stage::Part(part) => ("part", Some(part)),
+			};
+
+			JsonObject::new()
+				.number("day", comparison.day)
+				.string("stage", stage)
+				.optional_number("part", part)
+				.optional_number(
+					"baseline_ns",
+					comparison.baseline.map(|baseline| baseline.as_nanos()),
+				)
+				.number("median_ns", comparison.median.as_nanos())
+				.optional_number(
+					"change_percent",
+					comparison
+						.change
+						.map(|change| (change * 10.0).round() / 10.0),
+				)
+				.bool("regressed", comparison.regressed)
+		})),
+	}
+}
+
+/// Print the results as a table, one row per part, or nothing if every day failed
+fn print_table(results: &[PartResult]) {
+	if results.is_empty() {
+		return;
+	}
+
+	let answer_width = results
+		.iter()
+		.map(|result| result.answer.to_string().len())
+		.max()
+		.unwrap_or(0)
+		.max("answer".len());
+
+	println!(
+		"{:>3}  {:>4}  {:<width$}  {:>10}",
+		"day",
+		"part",
+		"answer",
+		"elapsed",
+		width = answer_width
+	);
+	for result in results {
+		println!(
+			"{:>3}  {:>4}  {:<width$}  {:>10}",
+			result.day,
+			result.part,
+			result.answer.to_string(),
+			timing::format_duration(result.elapsed),
+			width = answer_width
+		);
+	}
+}
+
+/// Print the answers and timing statistics of each stage of a day
+fn print_timing_table(day: &DayTimings) {
+	println!("Day {}", day.day);
+	for timing in &day.stages {
+		if let (Stage::Part(part), Some(answer)) = (timing.stage, &timing.answer) {
+			println!("Part {}: {}", part, answer);
+		}
+	}
+
+	let counted = day.stages.iter().any(|timing| timing.allocations.is_some());
+	print!(
+		"  {:<8} {:>10} {:>10} {:>10}",
+		"stage", "min", "median", "mean"
+	);
+	if counted {
+		print!(" {:>10} {:>10} {:>10}", "allocs", "allocated", "peak");
+	}
+	println!();
+	for timing in &day.stages {
+		print!(
+			"  {:<8} {:>10} {:>10} {:>10}",
+			timing.stage.to_string(),
+			timing::format_duration(timing.stats.min),
+			timing::format_duration(timing.stats.median),
+			timing::format_duration(timing.stats.mean),
+		);
+		if let Some(allocations) = timing.allocations {
+			print!(
+				" {:>10} {:>10} {:>10}",
+				allocations.count,
+				memory::format_bytes(allocations.bytes),
+				memory::format_bytes(allocations.peak),
+			);
+		}
+		println!();
+	}
+}
+
+/// Print records as a JSON array, one record per line
+fn print_json(records: impl Iterator<Item = JsonObject>) {
+	let records = records
+		.map(|record| format!("  {}", record.finish()))
+		.collect::<Vec<_>>();
+
+	if records.is_empty() {
+		println!("[]");
+	} else {
+		println!("[\n{}\n]", records.join(",\n"));
+	}
+}
+
+/// Minimal writer for flat JSON objects
+struct JsonObject {
+	fields: Vec<String>,
+}
+
+impl JsonObject {
+	fn new() -> Self {
+		JsonObject { fields: Vec::new() }
+	}
+
+	fn field(mut self, key: &str, value: String) -> Self {
+		self.fields.push(format!("{}: {}", escape(key), value));
+		self
+	}
+
+	fn string(self, key: &str, value: &str) -> Self {
+		self.field(key, escape(value))
+	}
+
+	fn number(self, key: &str, value: impl ToString) -> Self {
+		self.field(key, value.to_string())
+	}
+
+	fn bool(self, key: &str, value: bool) -> Self {
+		self.field(key, value.to_string())
+	}
+
+	fn optional_string(self, key: &str, value: Option<&str>) -> Self {
+		match value {
+			Some(value) => self.string(key, value),
+			None => self.field(key, "null".to_string()),
+		}
+	}
+
+	fn optional_number(self, key: &str, value: Option<impl ToString>) -> Self {
+		match value {
+			Some(value) => self.number(key, value),
+			None => self.field(key, "null".to_string()),
+		}
+	}
+
+	fn finish(self) -> String {
+		format!("{{{}}}", self.fields.join(", "))
+	}
+}
+
+/// Quote and escape a string as a JSON string literal
+fn escape(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len() + 2);
+	escaped.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => escaped.push_str("\\\""),
+			'\\' => escaped.push_str("\\\\"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			'\t' => escaped.push_str("\\t"),
+			c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+			c => escaped.push(c),
+		}
+	}
+	escaped.push('"');
+	escaped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escapes_quotes_and_backslashes() {
+		assert_eq!(escape(r#"say "hi""#), r#""say \"hi\"""#);
+		assert_eq!(escape(r"C:\inputs\day01"), r#""C:\\inputs\\day01""#);
+	}
+
+	#[test]
+	fn escapes_control_characters() {
+		assert_eq!(escape("a\nb\r\tc"), r#""a\nb\r\tc""#);
+		assert_eq!(escape("\u{0}\u{1b}\u{1f}"), r#""\u0000\u001b\u001f""#);
+	}
+
+	#[test]
+	fn keeps_other_characters() {
+		assert_eq!(escape(""), r#""""#);
+		assert_eq!(escape("µs ٣ \u{7f} /"), "\"µs ٣ \u{7f} /\"");
+	}
+
+	#[test]
+	fn writes_objects_with_escaped_keys_and_values() {
+		let record = JsonObject::new()
+			.number("day", 7)
+			.string("answer", "\"1\"\n")
+			.optional_string("input", None)
+			.optional_number("part", Some(2))
+			.bool("timed_out", false)
+			.string("a\"b", "");
+
+		assert_eq!(
+			record.finish(),
+			r#"{"day": 7, "answer": "\"1\"\n", "input": null, "part": 2, "timed_out": false, "a\"b": ""}"#
+		);
+	}
+}
@@ -0,0 +1,57 @@
+use std::ops::RangeInclusive;
+
+/// A small, seedable pseudo random number generator (SplitMix64)
+///
+/// The same seed always produces the same sequence, so generated inputs can be reproduced.
+#[derive(Debug, Clone)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		Rng { state: seed }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^ (z >> 31)
+	}
+
+	/// A number in `0..bound`
+	///
+	/// Panics if `bound` is 0.
+	pub fn below(&mut self, bound: usize) -> usize {
+		assert!(bound > 0, "empty range");
+		((self.next_u64() as u128 * bound as u128) >> 64) as usize
+	}
+
+	/// A number in the inclusive range
+	///
+	/// Panics if the range is empty.
+	pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+		let (start, end) = range.into_inner();
+		assert!(start <= end, "empty range");
+		start + self.below(end - start + 1)
+	}
+
+	/// `true` with a probability of `numerator / denominator`
+	pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+		self.below(denominator) < numerator
+	}
+
+	/// A random element of a non-empty slice
+	pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+		&items[self.below(items.len())]
+	}
+
+	/// Shuffle a slice in place
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i + 1));
+		}
+	}
+}
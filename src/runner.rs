@@ -0,0 +1,415 @@
+use std::{
+	any::Any,
+	collections::BTreeMap,
+	fmt, fs, io,
+	panic::{self, AssertUnwindSafe},
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
+
+use crate::{
+	cancel::{self, Cancelled},
+	days::{Day, Part},
+	error::Error,
+	input::{self, InputSource},
+	params::Overrides,
+	pool, timing,
+};
+
+/// How solving a part ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+	Solved(String),
+	/// The part was cancelled after its time limit
+	TimedOut(Duration),
+}
+
+impl Answer {
+	/// The answer, `None` if the part timed out
+	pub fn solved(&self) -> Option<&str> {
+		match self {
+			Answer::Solved(answer) => Some(answer),
+			Answer::TimedOut(_) => None,
+		}
+	}
+}
+
+impl fmt::Display for Answer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Answer::Solved(answer) => write!(f, "{}", answer),
+			Answer::TimedOut(limit) => {
+				write!(f, "timed out after {}", timing::format_duration(*limit))
+			}
+		}
+	}
+}
+
+/// The answer to one part of a day
+#[derive(Debug, Clone)]
+pub struct PartResult {
+	pub day: u8,
+	pub part: Part,
+	pub answer: Answer,
+	/// Time spent solving the part, excluding reading and parsing the input
+	pub elapsed: Duration,
+	/// Name of the input, see [`InputSource::name`]
+	pub input: String,
+	/// Fingerprint of the input, see [`input::fingerprint`]
+	pub fingerprint: String,
+}
+
+impl PartResult {
+	/// Whether the part was cancelled after its time limit
+	pub fn timed_out(&self) -> bool {
+		matches!(self.answer, Answer::TimedOut(_))
+	}
+}
+
+/// Time limits of the parts, by day and part
+///
+/// The most specific limit applies: that of the part, then that of its day, then the default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeouts {
+	default: Option<Duration>,
+	days: BTreeMap<u8, Duration>,
+	parts: BTreeMap<(u8, Part), Duration>,
+}
+
+impl Timeouts {
+	/// Add a limit given as `<seconds>`, `<day>=<seconds>` or `<day>:<part>=<seconds>`
+	pub fn add(&mut self, spec: &str) -> Result<(), String> {
+		let invalid = || format!("invalid timeout `{}`", spec);
+		let (target, seconds) = match spec.split_once('=') {
+			Some((target, seconds)) => (Some(target), seconds),
+			None => (None, spec),
+		};
+		let limit = seconds
+			.parse::<f64>()
+			.ok()
+			.filter(|seconds| *seconds > 0.0)
+			.and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+			.ok_or_else(invalid)?;
+
+		let Some(target) = target else {
+			self.default = Some(limit);
+			return Ok(());
+		};
+		let (day, part) = match target.split_once(':') {
+			Some((day, part)) => (day, Some(part)),
+			None => (target, None),
+		};
+		let day = day.parse::<u8>().map_err(|_| invalid())?;
+		match part {
+			None => {
+				self.days.insert(day, limit);
+			}
+			Some("1") => {
+				self.parts.insert((day, Part::One), limit);
+			}
+			Some("2") => {
+				self.parts.insert((day, Part::Two), limit);
+			}
+			Some(_) => return Err(invalid()),
+		}
+		Ok(())
+	}
+
+	/// The limit of a part, `None` if it may run forever
+	pub fn get(&self, day: u8, part: Part) -> Option<Duration> {
+		self.parts
+			.get(&(day, part))
+			.or_else(|| self.days.get(&day))
+			.copied()
+			.or(self.default)
+	}
+
+	/// Whether no part has a limit
+	pub fn is_empty(&self) -> bool {
+		self.default.is_none() && self.days.is_empty() && self.parts.is_empty()
+	}
+}
+
+/// Read, parse and solve the selected parts of a day
+///
+/// Each part may take as long as its limit in `timeouts`, see [`cancel`]. A part that takes
+/// longer is cancelled and answered with [`Answer::TimedOut`], the other parts still run.
+pub fn run_day(
+	day: &Day,
+	source: &InputSource,
+	part: Option<Part>,
+	overrides: &Overrides,
+	timeouts: &Timeouts,
+) -> Result<Vec<PartResult>, Error> {
+	let params = day.configure(overrides)?;
+	let content = source.read(day.number)?;
+	let parsed = day.parse(&content)?;
+	let fingerprint = input::fingerprint(&content);
+
+	let results = Part::selected(part)
+		.map(|part| {
+			let timeout = timeouts.get(day.number, part);
+			let start = Instant::now();
+			let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+				cancel::with_deadline(timeout, || {
+					day.solution.solve(parsed.as_ref(), params.as_ref(), part)
+				})
+			}));
+			let answer = match answer {
+				Ok(answer) => Answer::Solved(answer),
+				Err(payload) if payload.is::<Cancelled>() => {
+					Answer::TimedOut(timeout.unwrap_or_default())
+				}
+				Err(payload) => panic::resume_unwind(payload),
+			};
+
+			PartResult {
+				day: day.number,
+				part,
+				answer,
+				elapsed: start.elapsed(),
+				input: source.name(day.number),
+				fingerprint: fingerprint.clone(),
+			}
+		})
+		.collect();
+	Ok(results)
+}
+
+/// Run several days on a pool of `threads` workers, returning the results in the order of `days`
+///
+/// A failing day, including a solver panicking, does not affect the other days.
+pub fn run_days(
+	days: &[&Day],
+	source: &InputSource,
+	part: Option<Part>,
+	overrides: &Overrides,
+	timeouts: &Timeouts,
+	threads: usize,
+) -> Vec<Result<Vec<PartResult>, Error>> {
+	pool::map(days, threads, |day| {
+		run_day_isolated(day, source, part, overrides, timeouts)
+	})
+}
+
+/// The input files in a directory for a batch run, sorted by name
+///
+/// Hidden files and subdirectories are skipped.
+pub fn batch_inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+	let mut inputs = Vec::new();
+	let entries = fs::read_dir(dir).map_err(|error| {
+		io::Error::new(
+			error.kind(),
+			format!("unable to read `{}`: {}", dir.display(), error),
+		)
+	})?;
+	for entry in entries {
+		let entry = entry?;
+		if entry.file_type()?.is_file() && !entry.file_name().to_string_lossy().starts_with('.') {
+			inputs.push(entry.path());
+		}
+	}
+	inputs.sort();
+	Ok(inputs)
+}
+
+/// Run a day on every input file, returning the results in the order of `inputs`
+///
+/// A failing input, including a solver panicking on it, does not affect the other inputs.
+pub fn run_batch(
+	day: &Day,
+	inputs: &[PathBuf],
+	part: Option<Part>,
+	overrides: &Overrides,
+	timeouts: &Timeouts,
+	threads: usize,
+) -> Vec<Result<Vec<PartResult>, Error>> {
+	pool::map(inputs, threads, |path| {
+		let source = InputSource::File(path.clone());
+		run_day_isolated(day, &source, part, overrides, timeouts)
+	})
+}
+
+/// Run a day like [`run_day`], reporting a panicking solver as [`Error::Panicked`]
+fn run_day_isolated(
+	day: &Day,
+	source: &InputSource,
+	part: Option<Part>,
+	overrides: &Overrides,
+	timeouts: &Timeouts,
+) -> Result<Vec<PartResult>, Error> {
+	panic::catch_unwind(AssertUnwindSafe(|| {
+		run_day(day, source, part, overrides, timeouts)
+	}))
+	.unwrap_or_else(|payload| {
+		Err(Error::Panicked {
+			day: day.number,
+			message: panic_message(payload.as_ref()),
+		})
+	})
+}
+
+/// The message a panic was raised with
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		"unknown panic".to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{env, process, thread};
+
+	use super::*;
+	use crate::{
+		error::ParseError,
+		rng::Rng,
+		scan,
+		solution::{Example, InputSize, Solution},
+	};
+
+	/// Sleeps for as many milliseconds as its input says and answers with them, panics on 0
+	struct Sleepy;
+
+	impl Solution for Sleepy {
+		type Input = u64;
+		type PartOne = u64;
+		type PartTwo = u64;
+		type Params = ();
+
+		const EXAMPLES: &'static [Example] = &[];
+
+		const INPUT_SIZE: InputSize = InputSize {
+			unit: "inputs",
+			default: 1,
+			small: 1,
+		};
+
+		fn parse(input: &str) -> Result<u64, ParseError> {
+			scan::integer_token(input, input.trim_end())
+		}
+
+		fn part_one(millis: &u64, _params: &()) -> u64 {
+			assert!(*millis > 0, "no time to sleep");
+			thread::sleep(Duration::from_millis(*millis));
+			*millis
+		}
+
+		fn part_two(millis: &u64, params: &()) -> u64 {
+			Sleepy::part_one(millis, params)
+		}
+
+		fn generate(_rng: &mut Rng, _size: usize) -> String {
+			"1\n".to_string()
+		}
+
+		fn reference_one(input: &str, _params: &()) -> u64 {
+			input.trim().parse().unwrap()
+		}
+
+		fn reference_two(input: &str, params: &()) -> u64 {
+			Sleepy::reference_one(input, params)
+		}
+	}
+
+	/// A new empty directory for the files of a test
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	/// The answers of the first part, or the error
+	fn answers(results: Vec<Result<Vec<PartResult>, Error>>) -> Vec<Result<String, String>> {
+		results
+			.into_iter()
+			.map(|result| match result {
+				Ok(parts) => Ok(parts[0].answer.to_string()),
+				Err(error) => Err(error.to_string()),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn runs_days_in_order_and_isolates_panics() {
+		let dir = temp_dir("run-days");
+		for (day, millis) in [(1, "60"), (2, "0"), (3, "1"), (4, "20")] {
+			fs::write(dir.join(format!("day{:02}.txt", day)), millis).unwrap();
+		}
+		let days = (1..=4)
+			.map(|number| Day {
+				number,
+				solution: &Sleepy,
+			})
+			.collect::<Vec<_>>();
+
+		let results = run_days(
+			&days.iter().collect::<Vec<_>>(),
+			&InputSource::Directory(dir.clone()),
+			Some(Part::One),
+			&Overrides::default(),
+			&Timeouts::default(),
+			4,
+		);
+		fs::remove_dir_all(&dir).unwrap();
+
+		assert!(matches!(
+			results[1],
+			Err(Error::Panicked { day: 2, ref message }) if message == "no time to sleep"
+		));
+		assert_eq!(
+			answers(results),
+			[
+				Ok("60".to_string()),
+				Err("day 2 panicked: no time to sleep".to_string()),
+				Ok("1".to_string()),
+				Ok("20".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn runs_batches_on_the_visible_files() {
+		let dir = temp_dir("run-batch");
+		fs::write(dir.join("b.txt"), "oops").unwrap();
+		fs::write(dir.join("a.txt"), "2").unwrap();
+		fs::write(dir.join("c"), "1\n").unwrap();
+		fs::write(dir.join(".hidden"), "3").unwrap();
+		fs::create_dir(dir.join("nested")).unwrap();
+		fs::write(dir.join("nested").join("d.txt"), "4").unwrap();
+
+		let inputs = batch_inputs(&dir).unwrap();
+		let day = Day {
+			number: 1,
+			solution: &Sleepy,
+		};
+		let results = run_batch(
+			&day,
+			&inputs,
+			Some(Part::One),
+			&Overrides::default(),
+			&Timeouts::default(),
+			2,
+		);
+		fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(
+			inputs,
+			[dir.join("a.txt"), dir.join("b.txt"), dir.join("c")]
+		);
+		assert!(matches!(results[1], Err(Error::Parse { day: 1, .. })));
+		assert_eq!(
+			answers(results),
+			[
+				Ok("2".to_string()),
+				Err("day 1, line 1, column 1: expected a number, found `o`".to_string()),
+				Ok("1".to_string()),
+			]
+		);
+	}
+}
@@ -0,0 +1,195 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use advent_of_code_2024::Error;
+
+/// Template of a new day module
+const TEMPLATE: &str = include_str!("../templates/day.rs.in");
+
+/// Template of the reference solutions of a new day
+const REFERENCE_TEMPLATE: &str = include_str!("../templates/reference.rs.in");
+
+/// The crate root new days are created in by default
+pub fn default_root() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Create `src/days/dayNN` below `root` and register the day in `src/days/mod.rs`
+///
+/// Returns the paths of the created files.
+pub fn new_day(root: &Path, number: u8) -> Result<Vec<PathBuf>, Error> {
+	if !(1..=25).contains(&number) {
+		return Err(Error::Usage(format!(
+			"day {} is not between 1 and 25",
+			number
+		)));
+	}
+
+	let days_dir = root.join("src").join("days");
+	let registry_path = days_dir.join("mod.rs");
+	let registry = fs::read_to_string(&registry_path)?;
+	let registry = register(&registry, number).ok_or_else(|| {
+		Error::Scaffold(format!(
+			"day {} is already registered in {}",
+			number,
+			registry_path.display()
+		))
+	})?;
+
+	let day_dir = days_dir.join(format!("day{:02}", number));
+	if day_dir.exists() {
+		return Err(Error::Scaffold(format!(
+			"{} already exists",
+			day_dir.display()
+		)));
+	}
+
+	let module = TEMPLATE
+		.replace("{{day}}", &format!("{:02}", number))
+		.replace("{{number}}", &number.to_string());
+	let module_path = day_dir.join("mod.rs");
+	let reference_path = day_dir.join("reference.rs");
+	let input_path = day_dir.join("input");
+	let example_path = day_dir.join("example");
+
+	fs::create_dir_all(&day_dir)?;
+	fs::write(&module_path, module)?;
+	fs::write(&reference_path, REFERENCE_TEMPLATE)?;
+	fs::write(&input_path, "")?;
+	fs::write(&example_path, "")?;
+	fs::write(&registry_path, registry)?;
+
+	Ok(vec![
+		module_path,
+		reference_path,
+		input_path,
+		example_path,
+		registry_path,
+	])
+}
+
+/// Declare a day's module and insert the day into the `days!` list of the registry, keeping
+/// both ordered
+///
+/// Returns `None` if the day is already registered.
+fn register(registry: &str, number: u8) -> Option<String> {
+	let start = registry.find("days! {\n")? + "days! {\n".len();
+	let end = start + registry[start..].find("\n}")? + 1;
+
+	let mut entries = registry[start..end]
+		.lines()
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| {
+			let day = line
+				.trim()
+				.split(' ')
+				.next()
+				.and_then(|day| day.parse().ok());
+			(day.unwrap_or(0), line.to_string())
+		})
+		.collect::<Vec<(u8, String)>>();
+
+	if entries.iter().any(|(day, _)| *day == number) {
+		return None;
+	}
+	entries.push((
+		number,
+		format!("\t{} => day{:02}::Day{:02},", number, number, number),
+	));
+	entries.sort_by_key(|(day, _)| *day);
+
+	let mut updated = declare(&registry[..start], number);
+	for (_, line) in entries {
+		updated.push_str(&line);
+		updated.push('\n');
+	}
+	updated.push_str(&registry[end..]);
+	Some(updated)
+}
+
+/// Insert `pub mod dayNN;` among the day module declarations, keeping them ordered
+///
+/// Without any declarations yet, it goes in front of everything else.
+fn declare(registry: &str, number: u8) -> String {
+	let module = format!("day{:02}", number);
+	let declaration = format!("pub mod {};\n", module);
+
+	let mut offset = 0;
+	let mut position = None;
+	for line in registry.split_inclusive('\n') {
+		if let Some(declared) = line
+			.strip_prefix("pub mod day")
+			.and_then(|rest| rest.strip_suffix(";\n"))
+		{
+			position = Some(offset + line.len());
+			if format!("day{}", declared) > module {
+				position = Some(offset);
+				break;
+			}
+		}
+		offset += line.len();
+	}
+
+	let position = position.unwrap_or(0);
+	format!(
+		"{}{}{}",
+		&registry[..position],
+		declaration,
+		&registry[position..]
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const REGISTRY: &str = "\
+pub mod day01;
+pub mod day03;
+
+use crate::Day;
+
+days! {
+	1 => day01::Day01,
+	3 => day03::Day03,
+}
+";
+
+	#[test]
+	fn registers_days_in_order() {
+		assert_eq!(
+			register(REGISTRY, 2).unwrap(),
+			"\
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+use crate::Day;
+
+days! {
+	1 => day01::Day01,
+	2 => day02::Day02,
+	3 => day03::Day03,
+}
+"
+		);
+
+		let registry = register(REGISTRY, 12).unwrap();
+		assert!(registry.starts_with("pub mod day01;\npub mod day03;\npub mod day12;\n\n"));
+		assert!(registry.ends_with("\t3 => day03::Day03,\n\t12 => day12::Day12,\n}\n"));
+	}
+
+	#[test]
+	fn rejects_registered_days() {
+		assert_eq!(register(REGISTRY, 3), None);
+	}
+
+	#[test]
+	fn registers_days_in_the_registry_of_this_crate() {
+		let registry = register(include_str!("days/mod.rs"), 25).unwrap();
+		assert!(registry.contains("pub mod day09;\npub mod day25;\n"));
+		assert!(registry.contains("\t25 => day25::Day25,\n}"));
+	}
+}
@@ -0,0 +1,285 @@
+use std::fmt;
+
+use crate::error::ParseError;
+
+/// An integer type the [`Scanner`] can read
+pub trait Integer: Copy + fmt::Display {
+	/// Whether the type accepts a leading `-`
+	const SIGNED: bool;
+	const ZERO: Self;
+	const MIN: Self;
+	const MAX: Self;
+
+	/// `self * 10 + digit`, or `self * 10 - digit` for negative numbers, if it does not overflow
+	fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! integers {
+	($signed:literal => $($integer:ty),*) => {$(
+		impl Integer for $integer {
+			const SIGNED: bool = $signed;
+			const ZERO: Self = 0;
+			const MIN: Self = <$integer>::MIN;
+			const MAX: Self = <$integer>::MAX;
+
+			fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+				let shifted = self.checked_mul(10)?;
+				if negative {
+					shifted.checked_sub(digit as Self)
+				} else {
+					shifted.checked_add(digit as Self)
+				}
+			}
+		}
+	)*};
+}
+
+integers!(false => u8, u16, u32, u64, u128, usize);
+integers!(true => i8, i16, i32, i64, i128, isize);
+
+/// A cursor over the bytes of an input, reading integers and literals
+///
+/// Errors are located relative to the whole input.
+pub struct Scanner<'a> {
+	input: &'a [u8],
+	offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+	pub fn new(input: &'a [u8]) -> Self {
+		Scanner { input, offset: 0 }
+	}
+
+	/// The offset of the next byte
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Whether all of the input has been consumed
+	pub fn is_empty(&self) -> bool {
+		self.offset >= self.input.len()
+	}
+
+	/// The next byte, without consuming it
+	pub fn peek(&self) -> Option<u8> {
+		self.input.get(self.offset).copied()
+	}
+
+	/// Whether the next byte ends a line or the input
+	pub fn at_line_end(&self) -> bool {
+		matches!(self.peek(), None | Some(b'\n'))
+	}
+
+	/// Consume any run of the given separator bytes, returning how many were consumed
+	pub fn skip(&mut self, separators: &[u8]) -> usize {
+		let start = self.offset;
+		while self.peek().is_some_and(|byte| separators.contains(&byte)) {
+			self.offset += 1;
+		}
+		self.offset - start
+	}
+
+	/// Consume `literal` if the input continues with it
+	pub fn eat(&mut self, literal: &[u8]) -> bool {
+		let matches = self.input[self.offset..].starts_with(literal);
+		if matches {
+			self.offset += literal.len();
+		}
+		matches
+	}
+
+	/// Consume `literal`, or fail with `expected`
+	pub fn expect(&mut self, literal: &[u8], expected: &str) -> Result<(), ParseError> {
+		if self.eat(literal) {
+			Ok(())
+		} else {
+			Err(self.error(expected))
+		}
+	}
+
+	/// Consume the end of a line, which may also be the end of the input
+	pub fn expect_line_end(&mut self) -> Result<(), ParseError> {
+		if self.is_empty() || self.eat(b"\n") {
+			Ok(())
+		} else {
+			Err(self.error("end of line"))
+		}
+	}
+
+	/// Consume a single decimal digit
+	pub fn digit(&mut self) -> Result<u8, ParseError> {
+		match self.peek() {
+			Some(byte @ b'0'..=b'9') => {
+				self.offset += 1;
+				Ok(byte - b'0')
+			}
+			_ => Err(self.error("a digit")),
+		}
+	}
+
+	/// Consume a decimal integer, with a leading `-` for signed types
+	pub fn integer<T: Integer>(&mut self) -> Result<T, ParseError> {
+		let start = self.offset;
+		let negative = T::SIGNED && self.eat(b"-");
+		let digits = self.offset;
+
+		let mut value = Some(T::ZERO);
+		while let Some(byte @ b'0'..=b'9') = self.peek() {
+			value = value.and_then(|value| value.push_digit(byte - b'0', negative));
+			self.offset += 1;
+		}
+
+		if self.offset == digits {
+			return Err(self.error("a number"));
+		}
+		value.ok_or_else(|| {
+			let mut error = ParseError::at(
+				self.input,
+				start,
+				format!("a number between {} and {}", T::MIN, T::MAX),
+			);
+			error.found = format!(
+				"`{}`",
+				String::from_utf8_lossy(&self.input[start..self.offset])
+			);
+			error
+		})
+	}
+
+	/// Skip anything that cannot start an integer, then consume the integer
+	///
+	/// Returns `None` once the input is exhausted.
+	pub fn next_integer<T: Integer>(&mut self) -> Option<Result<T, ParseError>> {
+		while let Some(byte) = self.peek() {
+			let negative = T::SIGNED
+				&& byte == b'-'
+				&& self
+					.input
+					.get(self.offset + 1)
+					.is_some_and(u8::is_ascii_digit);
+			if byte.is_ascii_digit() || negative {
+				return Some(self.integer());
+			}
+			self.offset += 1;
+		}
+		None
+	}
+
+	/// Error for the next byte
+	pub fn error(&self, expected: impl Into<String>) -> ParseError {
+		ParseError::at(self.input, self.offset, expected)
+	}
+}
+
+/// Parse `token`, a slice of `input`, as a single integer
+///
+/// Errors are located in `input`.
+pub fn integer_token<T: Integer>(input: &str, token: &str) -> Result<T, ParseError> {
+	let start = token.as_ptr() as usize - input.as_ptr() as usize;
+	let mut scanner = Scanner {
+		input: input.as_bytes(),
+		offset: start,
+	};
+	let value = scanner.integer()?;
+	if scanner.offset() != start + token.len() {
+		return Err(scanner.error("end of number"));
+	}
+	Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Read a single integer from the start of `input`
+	fn read<T: Integer>(input: &str) -> Result<T, ParseError> {
+		Scanner::new(input.as_bytes()).integer()
+	}
+
+	#[test]
+	fn reads_unsigned_integers_up_to_their_maximum() {
+		assert_eq!(read::<u64>("0"), Ok(0));
+		assert_eq!(read::<u64>("007"), Ok(7));
+		assert_eq!(read::<u64>("18446744073709551615"), Ok(u64::MAX));
+		assert_eq!(read::<u8>("255 1"), Ok(255));
+	}
+
+	#[test]
+	fn rejects_overflowing_integers_with_the_whole_number() {
+		let error = read::<u64>("18446744073709551616").unwrap_err();
+		assert_eq!((error.line, error.column), (1, 1));
+		assert_eq!(
+			error.expected,
+			"a number between 0 and 18446744073709551615"
+		);
+		assert_eq!(error.found, "`18446744073709551616`");
+
+		assert!(read::<u8>("256").is_err());
+		assert!(read::<i8>("-129").is_err());
+	}
+
+	#[test]
+	fn reads_signed_integers_down_to_their_minimum() {
+		assert_eq!(read::<i32>("-42"), Ok(-42));
+		assert_eq!(read::<i32>("42"), Ok(42));
+		assert_eq!(read::<i64>("-9223372036854775808"), Ok(i64::MIN));
+		assert_eq!(read::<i64>("9223372036854775807"), Ok(i64::MAX));
+		assert!(read::<i64>("9223372036854775808").is_err());
+	}
+
+	#[test]
+	fn unsigned_integers_have_no_sign() {
+		let error = read::<u32>("-1").unwrap_err();
+		assert_eq!(error.expected, "a number");
+		assert_eq!(error.found, "`-`");
+	}
+
+	#[test]
+	fn rejects_empty_and_non_digit_tokens() {
+		let error = read::<u32>("").unwrap_err();
+		assert_eq!(error.expected, "a number");
+		assert_eq!(error.found, "end of input");
+
+		let error = read::<i32>("-x").unwrap_err();
+		assert_eq!((error.line, error.column), (1, 2));
+		assert_eq!(error.found, "`x`");
+
+		assert!(integer_token::<u32>("12a", "12a").is_err());
+		assert!(integer_token::<u32>("", "").is_err());
+	}
+
+	#[test]
+	fn locates_errors_by_line_and_column() {
+		let input = "1 2\n3 x\n";
+		let mut scanner = Scanner::new(input.as_bytes());
+		let mut numbers = Vec::new();
+		let error = loop {
+			match scanner.integer::<u32>() {
+				Ok(number) => numbers.push(number),
+				Err(error) => break error,
+			}
+			scanner.skip(b" \n");
+		};
+
+		assert_eq!(numbers, [1, 2, 3]);
+		assert_eq!((error.line, error.column), (2, 3));
+		assert_eq!(error.found, "`x`");
+	}
+
+	#[test]
+	fn locates_token_errors_in_the_whole_input() {
+		let input = "a: 1\nb: 99999999999";
+		let token = &input[input.len() - 11..];
+		let error = integer_token::<u32>(input, token).unwrap_err();
+		assert_eq!((error.line, error.column), (2, 4));
+		assert_eq!(error.found, "`99999999999`");
+	}
+
+	#[test]
+	fn next_integer_skips_everything_else() {
+		let mut scanner = Scanner::new(b"p=-3,4 v=x-");
+		assert_eq!(scanner.next_integer::<i32>(), Some(Ok(-3)));
+		assert_eq!(scanner.next_integer::<i32>(), Some(Ok(4)));
+		assert_eq!(scanner.next_integer::<i32>(), None);
+	}
+}
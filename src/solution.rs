@@ -0,0 +1,175 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+	days::Part,
+	error::ParseError,
+	input,
+	params::{Overrides, Param, Parameters, Values},
+	rng::Rng,
+};
+
+/// A worked example from the puzzle description with its published answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+	pub input: &'static str,
+	/// The answer of the first part, if the puzzle gives one for this example
+	pub part_one: Option<&'static str>,
+	/// The answer of the second part, if the puzzle gives one for this example
+	pub part_two: Option<&'static str>,
+}
+
+impl Example {
+	/// The published answer of a part
+	pub fn answer(&self, part: Part) -> Option<&'static str> {
+		match part {
+			Part::One => self.part_one,
+			Part::Two => self.part_two,
+		}
+	}
+}
+
+/// What the size of a generated input counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputSize {
+	/// The unit of the size, e.g. `lines`
+	pub unit: &'static str,
+	/// The size used unless one is given, about the size of the real inputs
+	pub default: usize,
+	/// The largest size used for differential tests, small enough for the reference solutions
+	pub small: usize,
+}
+
+/// A puzzle solution split into a parse stage and one stage per part
+pub trait Solution {
+	/// The parsed puzzle input shared by both parts
+	type Input;
+	/// The answer of the first part
+	type PartOne: Display;
+	/// The answer of the second part
+	type PartTwo: Display;
+	/// The tunable parameters of both parts, `()` if there are none
+	type Params: Parameters;
+
+	/// The examples of the puzzle description, solved with the default parameters
+	const EXAMPLES: &'static [Example];
+
+	/// What the size passed to [`Solution::generate`] counts
+	const INPUT_SIZE: InputSize;
+
+	/// Parse the normalised puzzle input, see [`Solution::parse_input`]
+	fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+	/// Normalise the raw puzzle input with [`input::normalise`] and parse it
+	///
+	/// Parse errors are located in the raw input.
+	fn parse_input(input: &str) -> Result<Self::Input, ParseError> {
+		let normalised = input::normalise(input);
+		Self::parse(&normalised.content).map_err(|error| normalised.locate(error))
+	}
+
+	/// Solve the first part on the parsed input
+	fn part_one(input: &Self::Input, params: &Self::Params) -> Self::PartOne;
+
+	/// Solve the second part on the parsed input
+	fn part_two(input: &Self::Input, params: &Self::Params) -> Self::PartTwo;
+
+	/// Generate a random input in the format [`Solution::parse`] accepts
+	///
+	/// `size` is at least 1.
+	fn generate(rng: &mut Rng, size: usize) -> String;
+
+	/// Solve the first part straight from the normalised input, as simply as possible
+	///
+	/// Only used to check [`Solution::part_one`] against, see [`crate::differential`]. It may be
+	/// slow and may panic on inputs [`Solution::parse`] rejects.
+	fn reference_one(input: &str, params: &Self::Params) -> Self::PartOne;
+
+	/// Solve the second part straight from the normalised input, see [`Solution::reference_one`]
+	fn reference_two(input: &str, params: &Self::Params) -> Self::PartTwo;
+}
+
+/// Object safe view of a [`Solution`], so days can be stored and run uniformly
+pub trait DynSolution: Sync {
+	/// The declared parameters of the solution
+	fn params(&self) -> &'static [Param];
+
+	/// The examples of the puzzle description
+	fn examples(&self) -> &'static [Example];
+
+	/// What the size of a generated input counts
+	fn input_size(&self) -> InputSize;
+
+	/// Generate a random input of the given size
+	fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+	/// Resolve the parameters from their overrides into a type erased value
+	fn configure(&self, overrides: &Overrides) -> Result<Box<dyn Any>, String>;
+
+	/// Normalise and parse the raw puzzle input into a type erased value
+	fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+	/// Solve a part on values previously returned by [`DynSolution::parse`] and
+	/// [`DynSolution::configure`]
+	fn solve(&self, input: &dyn Any, params: &dyn Any, part: Part) -> String;
+
+	/// Normalise the raw input and solve a part with the reference solution, see
+	/// [`Solution::reference_one`]
+	fn reference(&self, input: &str, params: &dyn Any, part: Part) -> String;
+}
+
+impl<S> DynSolution for S
+where
+	S: Solution + Sync,
+	S::Input: 'static,
+{
+	fn params(&self) -> &'static [Param] {
+		S::Params::DECLARED
+	}
+
+	fn examples(&self) -> &'static [Example] {
+		S::EXAMPLES
+	}
+
+	fn input_size(&self) -> InputSize {
+		S::INPUT_SIZE
+	}
+
+	fn generate(&self, rng: &mut Rng, size: usize) -> String {
+		S::generate(rng, size)
+	}
+
+	fn configure(&self, overrides: &Overrides) -> Result<Box<dyn Any>, String> {
+		let values = Values::new(S::Params::DECLARED, overrides);
+		Ok(Box::new(S::Params::from_values(&values)?))
+	}
+
+	fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+		Ok(Box::new(S::parse_input(input)?))
+	}
+
+	fn solve(&self, input: &dyn Any, params: &dyn Any, part: Part) -> String {
+		let input = input
+			.downcast_ref::<S::Input>()
+			.expect("input was parsed by a different solution");
+		let params = params
+			.downcast_ref::<S::Params>()
+			.expect("parameters were resolved by a different solution");
+
+		match part {
+			Part::One => S::part_one(input, params).to_string(),
+			Part::Two => S::part_two(input, params).to_string(),
+		}
+	}
+	fn reference(&self, input: &str, params: &dyn Any, part: Part) -> String {
+		let params = params
+			.downcast_ref::<S::Params>()
+			.expect("parameters were resolved by a different solution");
+		let input = input::normalise(input);
+		let input = &input.content;
+
+		match part {
+			Part::One => S::reference_one(input, params).to_string(),
+			Part::Two => S::reference_two(input, params).to_string(),
+		}
+	}
+}
@@ -0,0 +1,219 @@
+use std::{
+	fmt,
+	hint::black_box,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	days::{Day, Part},
+	error::Error,
+	input::{self, InputSource},
+	memory::{self, Allocations},
+	params::Overrides,
+};
+
+/// How often each stage is repeated when timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+	/// Untimed runs before measuring
+	pub warmup: usize,
+	/// Timed runs
+	pub runs: usize,
+}
+
+impl Default for Timing {
+	fn default() -> Self {
+		Timing {
+			warmup: 1,
+			runs: 10,
+		}
+	}
+}
+
+/// A separately timed stage of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+	Parse,
+	Part(Part),
+}
+
+impl fmt::Display for Stage {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Stage::Parse => write!(f, "parse"),
+			Stage::Part(part) => write!(f, "part {}", part),
+		}
+	}
+}
+
+/// Summary of the timed runs of a stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+	pub min: Duration,
+	pub median: Duration,
+	pub mean: Duration,
+}
+
+impl Stats {
+	/// Summarize a non-empty set of samples
+	pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+		samples.sort();
+
+		let middle = samples.len() / 2;
+		let median = if samples.len().is_multiple_of(2) {
+			(samples[middle - 1] + samples[middle]) / 2
+		} else {
+			samples[middle]
+		};
+		let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+
+		Stats {
+			min: samples[0],
+			median,
+			mean,
+		}
+	}
+}
+
+/// The timings of a stage, with the answer if the stage is a part
+#[derive(Debug, Clone)]
+pub struct StageTiming {
+	pub stage: Stage,
+	pub answer: Option<String>,
+	pub stats: Stats,
+	/// The allocations of one run, if they are counted, see [`memory::measure`]
+	pub allocations: Option<Allocations>,
+}
+
+/// The timings of all timed stages of a day
+#[derive(Debug, Clone)]
+pub struct DayTimings {
+	pub day: u8,
+	/// Name of the input, see [`InputSource::name`]
+	pub input: String,
+	/// Fingerprint of the input, see [`input::fingerprint`]
+	pub fingerprint: String,
+	pub stages: Vec<StageTiming>,
+}
+
+/// Run `stage` `timing.warmup` times, then time it `timing.runs` times
+pub fn measure<T>(timing: &Timing, mut stage: impl FnMut() -> T) -> Stats {
+	for _ in 0..timing.warmup {
+		black_box(stage());
+	}
+
+	let samples = (0..timing.runs.max(1))
+		.map(|_| {
+			let start = Instant::now();
+			let output = black_box(stage());
+			let elapsed = start.elapsed();
+			drop(output);
+			elapsed
+		})
+		.collect();
+
+	Stats::from_samples(samples)
+}
+
+/// Read a day's input, then time its parse stage and the selected parts
+pub fn time_day(
+	day: &Day,
+	source: &InputSource,
+	part: Option<Part>,
+	overrides: &Overrides,
+	timing: &Timing,
+) -> Result<DayTimings, Error> {
+	let params = day.configure(overrides)?;
+	let content = source.read(day.number)?;
+	// parse once up front, so malformed input is reported instead of timed
+	let parsed = day.parse(&content)?;
+
+	let mut stages = vec![StageTiming {
+		stage: Stage::Parse,
+		answer: None,
+		stats: measure(timing, || day.solution.parse(&content)),
+		allocations: memory::measure(|| day.solution.parse(&content)),
+	}];
+
+	for part in Part::selected(part) {
+		stages.push(StageTiming {
+			stage: Stage::Part(part),
+			answer: Some(day.solution.solve(parsed.as_ref(), params.as_ref(), part)),
+			stats: measure(timing, || {
+				day.solution.solve(parsed.as_ref(), params.as_ref(), part)
+			}),
+			allocations: memory::measure(|| {
+				day.solution.solve(parsed.as_ref(), params.as_ref(), part)
+			}),
+		});
+	}
+
+	Ok(DayTimings {
+		day: day.number,
+		input: source.name(day.number),
+		fingerprint: input::fingerprint(&content),
+		stages,
+	})
+}
+
+/// Format a duration with three significant digits and a fitting unit
+pub fn format_duration(duration: Duration) -> String {
+	let nanos = duration.as_nanos() as f64;
+	let (value, unit) = if nanos < 1e3 {
+		(nanos, "ns")
+	} else if nanos < 1e6 {
+		(nanos / 1e3, "µs")
+	} else if nanos < 1e9 {
+		(nanos / 1e6, "ms")
+	} else {
+		(nanos / 1e9, "s")
+	};
+
+	let precision = if value < 10.0 {
+		2
+	} else if value < 100.0 {
+		1
+	} else {
+		0
+	};
+	format!("{:.*} {}", precision, value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn millis(samples: &[u64]) -> Vec<Duration> {
+		samples.iter().copied().map(Duration::from_millis).collect()
+	}
+
+	#[test]
+	fn summarizes_an_odd_number_of_samples() {
+		let stats = Stats::from_samples(millis(&[9, 1, 5]));
+		assert_eq!(stats.min, Duration::from_millis(1));
+		assert_eq!(stats.median, Duration::from_millis(5));
+		assert_eq!(stats.mean, Duration::from_millis(5));
+	}
+
+	#[test]
+	fn averages_the_middle_samples_of_an_even_number() {
+		let stats = Stats::from_samples(millis(&[40, 10, 2, 21]));
+		assert_eq!(stats.min, Duration::from_millis(2));
+		assert_eq!(stats.median, Duration::from_micros(15_500));
+		assert_eq!(stats.mean, Duration::from_micros(18_250));
+	}
+
+	#[test]
+	fn summarizes_a_single_sample() {
+		let stats = Stats::from_samples(millis(&[3]));
+		let expected = Duration::from_millis(3);
+		assert_eq!(
+			stats,
+			Stats {
+				min: expected,
+				median: expected,
+				mean: expected,
+			}
+		);
+	}
+}
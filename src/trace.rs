@@ -0,0 +1,100 @@
+//! Leveled tracing of intermediate solver steps
+//!
+//! Solvers emit events with [`trace!`](crate::trace!), which are written to stderr when their
+//! level is enabled, e.g. with `aoc run -v`. Tracing is off by default. A disabled event costs a
+//! relaxed atomic load and a branch; its fields are not evaluated.
+
+use std::{
+	fmt::{self, Write as _},
+	io::{self, Write as _},
+	sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How much detail an event adds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+	/// The steps that decide an answer, such as each unsafe report or each moved file (`-v`)
+	Info = 1,
+	/// Everything else, such as each checked candidate (`-vv`)
+	Debug = 2,
+}
+
+/// The most detailed enabled level, 0 when tracing is off
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Enable events up to `level`, or disable tracing with `None`
+pub fn set_level(level: Option<Level>) {
+	LEVEL.store(level.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+/// Whether events of `level` are written
+#[inline]
+pub fn enabled(level: Level) -> bool {
+	LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Write an event to stderr as `target: event key=value ...`
+///
+/// Used by [`trace!`](crate::trace!), which checks the level first.
+pub fn emit(target: &str, event: &str, fields: &[(&str, &dyn fmt::Debug)]) {
+	// days::day03 -> day03
+	let target = target.rsplit("::").next().unwrap_or(target);
+
+	let mut line = format!("{}: {}", target, event);
+	for (key, value) in fields {
+		let _ = write!(line, " {}={:?}", key, value);
+	}
+	line.push('\n');
+
+	// a failing stderr must not fail the solver
+	let _ = io::stderr().lock().write_all(line.as_bytes());
+}
+
+/// Emit a trace event with `key = value` fields, if its level is enabled
+///
+/// The target is the module the event is emitted in, e.g.
+/// `trace!(Info, "mul", x = x, y = y)` in day 3 writes `day03: mul x=2 y=4`.
+#[macro_export]
+macro_rules! trace {
+	($level:ident, $event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+		if $crate::trace::enabled($crate::trace::Level::$level) {
+			$crate::trace::emit(
+				module_path!(),
+				$event,
+				&[$((stringify!($key), &$value as &dyn ::std::fmt::Debug)),*],
+			);
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn filters_events_by_level() {
+		set_level(None);
+		assert!(!enabled(Level::Info));
+		assert!(!enabled(Level::Debug));
+
+		set_level(Some(Level::Info));
+		assert!(enabled(Level::Info));
+		assert!(!enabled(Level::Debug));
+
+		let mut evaluated = false;
+		crate::trace!(
+			Debug,
+			"skipped",
+			value = {
+				evaluated = true;
+			}
+		);
+		assert!(!evaluated);
+
+		set_level(Some(Level::Debug));
+		assert!(enabled(Level::Info));
+		assert!(enabled(Level::Debug));
+
+		set_level(None);
+	}
+}
@@ -0,0 +1,339 @@
+use std::{
+	collections::BTreeMap,
+	fmt, fs, io,
+	path::{Path, PathBuf},
+};
+
+use crate::{
+	days::{Day, Part},
+	error::{Error, ParseError},
+	input::{self, InputSource},
+	params::Overrides,
+	scan,
+};
+
+/// The default answers file, checked into this repository
+pub fn default_path() -> PathBuf {
+	[env!("CARGO_MANIFEST_DIR"), "answers.txt"].iter().collect()
+}
+
+/// Key of a stored answer: day, part and input fingerprint
+type AnswerKey = (u8, Part, String);
+
+/// Expected answers per day, part and input
+///
+/// The file has one answer per line, as `<day> <part> <input fingerprint> <answer>`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+	answers: BTreeMap<AnswerKey, String>,
+}
+
+impl Answers {
+	/// Load the answers file, or start empty if it does not exist yet
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let content = match fs::read_to_string(path) {
+			Ok(content) => content,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+			Err(error) => return Err(error.into()),
+		};
+
+		Answers::parse(&content).map_err(|error| Error::Answers {
+			path: path.to_path_buf(),
+			error,
+		})
+	}
+
+	/// Parse the content of an answers file
+	fn parse(content: &str) -> Result<Self, ParseError> {
+		let mut answers = BTreeMap::new();
+
+		for line in content.lines() {
+			if line.trim().is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let mut fields = line.splitn(4, ' ');
+			let mut next = |expected| {
+				fields
+					.next()
+					.filter(|field| !field.is_empty())
+					.ok_or_else(|| ParseError::at_token(content, &line[line.len()..], expected))
+			};
+			let (day, part, fingerprint, answer) = (
+				next("a day")?,
+				next("a part")?,
+				next("an input")?,
+				next("an answer")?,
+			);
+
+			let day = scan::integer_token(content, day)?;
+			let part = match part {
+				"1" => Part::One,
+				"2" => Part::Two,
+				_ => return Err(ParseError::at_token(content, part, "`1` or `2`")),
+			};
+
+			answers.insert((day, part, fingerprint.to_string()), answer.to_string());
+		}
+
+		Ok(Answers { answers })
+	}
+
+	/// Write the answers file
+	pub fn save(&self, path: &Path) -> io::Result<()> {
+		let mut content = String::from("# <day> <part> <input fingerprint> <answer>\n");
+		for ((day, part, fingerprint), answer) in &self.answers {
+			content.push_str(&format!("{} {} {} {}\n", day, part, fingerprint, answer));
+		}
+		fs::write(path, content)
+	}
+
+	/// The stored answer for a part on an input
+	pub fn get(&self, day: u8, part: Part, fingerprint: &str) -> Option<&str> {
+		self.answers
+			.get(&(day, part, fingerprint.to_string()))
+			.map(String::as_str)
+	}
+
+	/// Store the answer for a part on an input
+	pub fn insert(&mut self, day: u8, part: Part, fingerprint: &str, answer: String) {
+		self.answers
+			.insert((day, part, fingerprint.to_string()), answer);
+	}
+}
+
+/// Result of checking one part against the stored answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+	Pass,
+	Fail { expected: String, actual: String },
+	Missing { actual: String },
+	Error(String),
+}
+
+impl fmt::Display for Outcome {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Outcome::Pass => write!(f, "pass"),
+			Outcome::Fail { expected, actual } => {
+				write!(f, "FAIL     expected {}, got {}", expected, actual)
+			}
+			Outcome::Missing { actual } => write!(f, "missing  got {}", actual),
+			Outcome::Error(message) => write!(f, "ERROR    {}", message),
+		}
+	}
+}
+
+/// Outcome of one part of one day
+#[derive(Debug, Clone)]
+pub struct Check {
+	pub day: u8,
+	pub part: Part,
+	pub fingerprint: Option<String>,
+	pub outcome: Outcome,
+}
+
+/// Solve both parts of a day and compare them with the stored answers
+pub fn verify_day(day: &Day, source: &InputSource, answers: &Answers) -> Vec<Check> {
+	let failed = |message: String| {
+		Part::ALL
+			.into_iter()
+			.map(|part| Check {
+				day: day.number,
+				part,
+				fingerprint: None,
+				outcome: Outcome::Error(message.clone()),
+			})
+			.collect()
+	};
+
+	// the stored answers are those of the default parameters
+	let params = match day.configure(&Overrides::default()) {
+		Ok(params) => params,
+		Err(error) => return failed(error.to_string()),
+	};
+	let content = match source.read(day.number) {
+		Ok(content) => content,
+		Err(error) => return failed(error.to_string()),
+	};
+	let parsed = match day.parse(&content) {
+		Ok(parsed) => parsed,
+		Err(error) => return failed(error.to_string()),
+	};
+	let fingerprint = input::fingerprint(&content);
+
+	Part::ALL
+		.into_iter()
+		.map(|part| {
+			let actual = day.solution.solve(parsed.as_ref(), params.as_ref(), part);
+			let outcome = match answers.get(day.number, part, &fingerprint) {
+				Some(expected) if expected == actual => Outcome::Pass,
+				Some(expected) => Outcome::Fail {
+					expected: expected.to_string(),
+					actual,
+				},
+				None => Outcome::Missing { actual },
+			};
+
+			Check {
+				day: day.number,
+				part,
+				fingerprint: Some(fingerprint.clone()),
+				outcome,
+			}
+		})
+		.collect()
+}
+
+/// Outcome of one part of one example of a day
+#[derive(Debug, Clone)]
+pub struct ExampleCheck {
+	pub day: u8,
+	/// Number of the example within the day, starting at 1
+	pub example: usize,
+	pub part: Part,
+	pub outcome: Outcome,
+}
+
+/// Solve the examples of a day and compare them with their published answers
+///
+/// Parts an example has no published answer for are skipped.
+pub fn check_examples(day: &Day, part: Option<Part>) -> Vec<ExampleCheck> {
+	let params = day.configure(&Overrides::default());
+	let mut checks = Vec::new();
+
+	for (index, example) in day.solution.examples().iter().enumerate() {
+		let parsed = day.parse(example.input);
+
+		for part in Part::selected(part) {
+			let Some(expected) = example.answer(part) else {
+				continue;
+			};
+
+			let outcome = match (&params, &parsed) {
+				(Ok(params), Ok(parsed)) => {
+					let actual = day.solution.solve(parsed.as_ref(), params.as_ref(), part);
+					if actual == expected {
+						Outcome::Pass
+					} else {
+						Outcome::Fail {
+							expected: expected.to_string(),
+							actual,
+						}
+					}
+				}
+				(Err(error), _) | (_, Err(error)) => Outcome::Error(error.to_string()),
+			};
+
+			checks.push(ExampleCheck {
+				day: day.number,
+				example: index + 1,
+				part,
+				outcome,
+			});
+		}
+	}
+
+	checks
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{env, process};
+
+	use super::*;
+	use crate::days;
+
+	#[test]
+	fn parses_answers_and_skips_comments() {
+		let answers = Answers::parse(
+			"# <day> <part> <input fingerprint> <answer>\n\n1 1 00ff 42\n  \n13 2 00ff two words  \n",
+		)
+		.unwrap();
+
+		assert_eq!(answers.get(1, Part::One, "00ff"), Some("42"));
+		assert_eq!(answers.get(13, Part::Two, "00ff"), Some("two words  "));
+		assert_eq!(answers.get(1, Part::Two, "00ff"), None);
+		assert_eq!(answers.get(1, Part::One, "ff00"), None);
+	}
+
+	#[test]
+	fn reports_the_location_of_invalid_lines() {
+		let error = Answers::parse("1 1 00ff 42\n2 3 00ff 7\n").unwrap_err();
+		assert_eq!((error.line, error.column), (2, 3));
+		assert_eq!(error.expected, "`1` or `2`");
+
+		let error = Answers::parse("1 1 00ff\n").unwrap_err();
+		assert_eq!((error.line, error.column), (1, 9));
+		assert_eq!(error.expected, "an answer");
+	}
+
+	#[test]
+	fn answers_survive_a_round_trip_through_the_file() {
+		let path = env::temp_dir().join(format!("aoc-answers-{}.txt", process::id()));
+		let mut answers = Answers::default();
+		answers.insert(7, Part::Two, "0123456789abcdef", "11387".to_string());
+		answers.insert(3, Part::One, "fedcba9876543210", "a b".to_string());
+		answers.save(&path).unwrap();
+
+		let loaded = Answers::load(&path);
+		fs::remove_file(&path).unwrap();
+		let loaded = loaded.unwrap();
+		assert_eq!(loaded.answers, answers.answers);
+	}
+
+	#[test]
+	fn compares_answers_per_part() {
+		let day = days::get(1).unwrap();
+		let example = day.solution.examples()[0].input;
+		let path = env::temp_dir().join(format!("aoc-verify-{}.txt", process::id()));
+		fs::write(&path, example).unwrap();
+		let source = InputSource::File(path.clone());
+		let fingerprint = input::fingerprint(example);
+
+		let mut answers = Answers::default();
+		answers.insert(1, Part::One, &fingerprint, "11".to_string());
+		answers.insert(1, Part::Two, &fingerprint, "30".to_string());
+		let checks = verify_day(day, &source, &answers);
+		let missing = verify_day(day, &source, &Answers::default());
+		fs::remove_file(&path).unwrap();
+
+		let outcomes = |checks: Vec<Check>| {
+			checks
+				.into_iter()
+				.map(|check| (check.part, check.outcome))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			outcomes(checks),
+			[
+				(Part::One, Outcome::Pass),
+				(
+					Part::Two,
+					Outcome::Fail {
+						expected: "30".to_string(),
+						actual: "31".to_string(),
+					}
+				),
+			]
+		);
+		assert_eq!(
+			outcomes(missing),
+			[
+				(
+					Part::One,
+					Outcome::Missing {
+						actual: "11".to_string()
+					}
+				),
+				(
+					Part::Two,
+					Outcome::Missing {
+						actual: "31".to_string()
+					}
+				),
+			]
+		);
+	}
+}
@@ -0,0 +1,145 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process, thread,
+	time::{Duration, SystemTime},
+};
+
+use advent_of_code_2024::{
+	days,
+	input::InputSource,
+	params::Overrides,
+	timing::{self, Timing},
+	Error, Part,
+};
+
+use crate::report::{self, Format};
+
+/// What `aoc watch` re-runs, and how
+#[derive(Debug)]
+pub struct Watch {
+	pub day: u8,
+	pub part: Option<Part>,
+	pub input: InputSource,
+	pub params: Overrides,
+	pub timing: Timing,
+	/// Time between two checks for changes
+	pub interval: Duration,
+	/// Also watch the sources of the day, and rebuild and run them with cargo on changes
+	pub rebuild: bool,
+	/// The crate the sources are watched and rebuilt in
+	pub root: PathBuf,
+}
+
+/// Run a day, then run it again whenever its input or, when rebuilding, its sources change
+///
+/// Only returns on errors that make watching impossible; failing runs are reported and watched on.
+pub fn watch(watch: &Watch) -> Result<(), Error> {
+	let day = days::get(watch.day)?;
+	day.configure(&watch.params)?;
+	let input = watch
+		.input
+		.path(day.number)
+		.expect("stdin is rejected by the command line parser");
+
+	let mut paths = vec![input];
+	if watch.rebuild {
+		paths.extend(sources(&watch.root, day.number, &paths[0])?);
+	}
+
+	let mut seen = modified(&paths);
+	let mut changed = None;
+	loop {
+		match changed {
+			None => println!("--- running day {}", day.number),
+			Some(path) => println!("--- {} changed, running day {}", path, day.number),
+		}
+		if watch.rebuild {
+			rebuild_and_run(watch);
+		} else {
+			match timing::time_day(day, &watch.input, watch.part, &watch.params, &watch.timing) {
+				Ok(timings) => report::print_timings(&[timings], Format::Text),
+				Err(error) => eprintln!("error: {}", error),
+			}
+		}
+
+		// wait for the next change, picking up files created in the meantime
+		changed = loop {
+			thread::sleep(watch.interval);
+			if watch.rebuild {
+				paths.truncate(1);
+				paths.extend(sources(&watch.root, day.number, &paths[0])?);
+			}
+			let current = modified(&paths);
+			if current != seen {
+				let path = current
+					.iter()
+					.find(|entry| !seen.contains(entry))
+					.or_else(|| seen.iter().find(|entry| !current.contains(entry)))
+					.map(|(path, _)| path.display().to_string());
+				seen = current;
+				break path;
+			}
+		};
+	}
+}
+
+/// The source files of a day, `src/days/dayNN/*` below the crate root, except for its input
+fn sources(root: &Path, day: u8, input: &Path) -> Result<Vec<PathBuf>, Error> {
+	let dir = root.join("src").join("days").join(format!("day{:02}", day));
+	let mut sources = Vec::new();
+	for entry in fs::read_dir(&dir)? {
+		let entry = entry?;
+		if entry.file_type()?.is_file() && entry.path() != input {
+			sources.push(entry.path());
+		}
+	}
+	sources.sort();
+	Ok(sources)
+}
+
+/// The modification time of every path, `None` for paths that cannot be read
+fn modified(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+	paths
+		.iter()
+		.map(|path| {
+			let time = fs::metadata(path).and_then(|metadata| metadata.modified());
+			(path.clone(), time.ok())
+		})
+		.collect()
+}
+
+/// Rebuild the crate with cargo and run the day with the fresh binary
+///
+/// Build errors are printed by cargo.
+fn rebuild_and_run(watch: &Watch) {
+	let mut command = process::Command::new(option_env!("CARGO").unwrap_or("cargo"));
+	command
+		.arg("run")
+		.arg("--release")
+		.arg("--quiet")
+		.arg("--manifest-path")
+		.arg(watch.root.join("Cargo.toml"))
+		.arg("--")
+		.arg("run")
+		.arg(watch.day.to_string())
+		.arg("--runs")
+		.arg(watch.timing.runs.to_string())
+		.arg("--warmup")
+		.arg(watch.timing.warmup.to_string());
+	match &watch.input {
+		InputSource::Directory(dir) => command.arg("--input-dir").arg(dir),
+		InputSource::File(path) => command.arg("--input").arg(path),
+		InputSource::Stdin => unreachable!("stdin is rejected before watching"),
+	};
+	if let Some(part) = watch.part {
+		command.arg("--part").arg(part.to_string());
+	}
+	for (name, value) in watch.params.iter() {
+		command.arg("--param").arg(format!("{}={}", name, value));
+	}
+
+	if let Err(error) = command.status() {
+		eprintln!("error: unable to run cargo: {}", error);
+	}
+}
@@ -0,0 +1,73 @@
+//! The benchmark baseline: stored medians survive a round trip through the file and slower
+//! stages are flagged
+
+use std::{env, fs, process, time::Duration};
+
+use advent_of_code_2024::{
+	bench::{self, Baseline},
+	timing::{DayTimings, Stage, StageTiming, Stats},
+	Part,
+};
+
+/// Timings of a day whose stages took the given medians
+fn timings(parse: u64, part_one: u64, part_two: u64) -> DayTimings {
+	let stage = |stage, median| {
+		let median = Duration::from_micros(median);
+		StageTiming {
+			stage,
+			answer: None,
+			stats: Stats {
+				min: median,
+				median,
+				mean: median,
+			},
+			allocations: None,
+		}
+	};
+
+	DayTimings {
+		day: 1,
+		input: "input".to_string(),
+		fingerprint: "0123456789abcdef".to_string(),
+		stages: vec![
+			stage(Stage::Parse, parse),
+			stage(Stage::Part(Part::One), part_one),
+			stage(Stage::Part(Part::Two), part_two),
+		],
+	}
+}
+
+#[test]
+fn slower_stages_regress_against_a_stored_baseline() {
+	let path = env::temp_dir().join(format!("aoc-benchmarks-{}.txt", process::id()));
+	let mut baseline = Baseline::load(&path).unwrap();
+	baseline.record(&timings(100, 200, 300));
+	baseline.save(&path).unwrap();
+
+	let baseline = Baseline::load(&path);
+	fs::remove_file(&path).unwrap();
+	let comparisons = bench::compare(&timings(105, 260, 150), &baseline.unwrap(), 10.0);
+
+	let regressed = comparisons
+		.iter()
+		.map(|comparison| comparison.regressed)
+		.collect::<Vec<_>>();
+	assert_eq!(regressed, [false, true, false]);
+	assert_eq!(comparisons[1].baseline, Some(Duration::from_micros(200)));
+	assert!((comparisons[1].change.unwrap() - 30.0).abs() < 1e-9);
+	assert!(comparisons[2].change.unwrap() < 0.0);
+}
+
+#[test]
+fn stages_on_other_inputs_have_no_baseline() {
+	let mut baseline = Baseline::default();
+	baseline.record(&timings(100, 200, 300));
+
+	let mut other = timings(1000, 2000, 3000);
+	other.fingerprint = "fedcba9876543210".to_string();
+	let comparisons = bench::compare(&other, &baseline, 10.0);
+
+	assert!(comparisons
+		.iter()
+		.all(|comparison| comparison.baseline.is_none() && !comparison.regressed));
+}
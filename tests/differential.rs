@@ -0,0 +1,25 @@
+//! The solutions, checked against their naive reference solutions on small generated inputs
+
+use advent_of_code_2024::{days::DAYS, differential, params::Overrides};
+
+#[test]
+fn solutions_agree_with_reference_solutions() {
+	let mut failures = Vec::new();
+	for day in DAYS {
+		let check = differential::check_day(day, None, 40, &Overrides::default()).unwrap();
+		if let Some(disagreement) = check.disagreement {
+			failures.push(format!(
+				"day {} part {} (--seed {} --size {}): {} instead of {} for\n{}",
+				day.number,
+				disagreement.part,
+				disagreement.seed,
+				disagreement.size,
+				disagreement.answer,
+				disagreement.reference,
+				disagreement.input
+			));
+		}
+	}
+
+	assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,60 @@
+//! The examples of the puzzle descriptions, checked against their published answers and
+//! parsed in different layouts
+
+use advent_of_code_2024::{
+	days::DAYS,
+	params::Overrides,
+	verify::{self, Outcome},
+	Part,
+};
+
+#[test]
+fn examples_match_published_answers() {
+	let failures = DAYS
+		.iter()
+		.flat_map(|day| verify::check_examples(day, None))
+		.filter(|check| check.outcome != Outcome::Pass)
+		.map(|check| {
+			format!(
+				"day {} example {} part {}: {}",
+				check.day, check.example, check.part, check.outcome
+			)
+		})
+		.collect::<Vec<_>>();
+
+	assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_day_has_an_example() {
+	for day in DAYS {
+		assert!(
+			!day.solution.examples().is_empty(),
+			"day {} has no examples",
+			day.number
+		);
+	}
+}
+
+#[test]
+fn examples_parse_the_same_with_crlf_and_blank_lines() {
+	for day in DAYS {
+		let params = day.configure(&Overrides::default()).unwrap();
+		for example in day.solution.examples() {
+			// blank lines around the input and CRLF line endings after trailing whitespace
+			let windows = format!("\r\n \n{}\r\n\r\n", example.input.replace('\n', " \t\r\n"));
+			let original = day.parse(example.input).unwrap();
+			let mangled = day.parse(&windows).unwrap();
+
+			for part in Part::ALL {
+				assert_eq!(
+					day.solution.solve(original.as_ref(), params.as_ref(), part),
+					day.solution.solve(mangled.as_ref(), params.as_ref(), part),
+					"day {} part {}",
+					day.number,
+					part
+				);
+			}
+		}
+	}
+}
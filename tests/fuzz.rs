@@ -0,0 +1,50 @@
+//! One fuzz target per day: mutated inputs must be rejected or solved, without panicking,
+//! overflowing or hanging
+
+use std::time::Duration;
+
+use advent_of_code_2024::{
+	days,
+	fuzz::{self, Fuzzing},
+};
+
+/// Fuzz a day briefly, the `fuzz` command runs longer campaigns
+fn fuzz_day(number: u8) {
+	let fuzzing = Fuzzing {
+		iterations: 2000,
+		seed: u64::from(number),
+		time_limit: Duration::from_secs(10),
+	};
+
+	if let Err(crash) = fuzz::fuzz_day(days::get(number).unwrap(), &fuzzing) {
+		panic!(
+			"day {} {} on {:?}",
+			crash.day,
+			crash.failure,
+			String::from_utf8_lossy(&crash.input)
+		);
+	}
+}
+
+macro_rules! fuzz_targets {
+	($($name:ident => $day:literal,)*) => {
+		$(
+			#[test]
+			fn $name() {
+				fuzz_day($day);
+			}
+		)*
+	};
+}
+
+fuzz_targets! {
+	day01 => 1,
+	day02 => 2,
+	day03 => 3,
+	day04 => 4,
+	day05 => 5,
+	day06 => 6,
+	day07 => 7,
+	day08 => 8,
+	day09 => 9,
+}
@@ -0,0 +1,36 @@
+//! Malformed inputs are reported as parse errors of their day, located in the raw input
+
+use advent_of_code_2024::{days, Error};
+
+/// The day, line, column and expected token of the error parsing `input`
+fn parse_error(day: u8, input: &str) -> (u8, usize, usize, String) {
+	match days::get(day).unwrap().parse(input) {
+		Err(Error::Parse { day, error }) => (day, error.line, error.column, error.expected),
+		Err(error) => panic!("expected a parse error, got {}", error),
+		Ok(_) => panic!("day {} parsed the malformed input", day),
+	}
+}
+
+#[test]
+fn day05_rules_need_a_bar() {
+	let input = "47|53\n97-13\n\n75,47,61\n";
+	assert_eq!(parse_error(5, input), (5, 2, 3, "`|`".to_string()));
+}
+
+#[test]
+fn day07_test_values_need_a_colon() {
+	let input = "190: 10 19\n3267 81 40 27\n";
+	assert_eq!(parse_error(7, input), (7, 2, 5, "`: `".to_string()));
+}
+
+#[test]
+fn day09_disk_maps_are_digits() {
+	let input = "23331331214x4131402\n";
+	assert_eq!(parse_error(9, input), (9, 1, 12, "a digit".to_string()));
+}
+
+#[test]
+fn errors_are_located_in_the_raw_input() {
+	let input = "\r\n190: 10 19\r\n3267 81 40 27\r\n";
+	assert_eq!(parse_error(7, input), (7, 3, 5, "`: `".to_string()));
+}